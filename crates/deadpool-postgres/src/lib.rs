@@ -172,6 +172,10 @@ impl managed::Manager for Manager {
     fn detach(&self, object: &mut ClientWrapper) {
         self.statement_caches.detach(&object.statement_cache);
     }
+
+    fn is_connection_error(&self, error: &Error) -> bool {
+        error.is_closed()
+    }
 }
 
 /// Describes a mechanism for establishing a connection to a PostgreSQL
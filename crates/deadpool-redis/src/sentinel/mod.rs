@@ -167,4 +167,8 @@ impl managed::Manager for Manager {
             Err(managed::RecycleError::message("Invalid PING response"))
         }
     }
+
+    fn is_connection_error(&self, error: &RedisError) -> bool {
+        error.is_unrecoverable_error()
+    }
 }
@@ -0,0 +1,147 @@
+//! Integration with the [`actix-web`](https://crates.io/crates/actix-web)
+//! web framework.
+//!
+//! This module provides the [`Conn`] extractor which retrieves an object
+//! from a [`Pool`] registered as application data. It also implements
+//! [`ResponseError`] for [`PoolError`] so errors returned by [`Pool::get()`]
+//! can be returned from handlers directly.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use actix_web::{App, web};
+//! use deadpool::{
+//!     actix_web::Conn,
+//!     managed::{self, Metrics, RecycleResult},
+//! };
+//!
+//! struct Manager {}
+//!
+//! impl managed::Manager for Manager {
+//!     type Type = usize;
+//!     type Error = std::convert::Infallible;
+//!
+//!     async fn create(&self) -> Result<usize, Self::Error> {
+//!         Ok(42)
+//!     }
+//!
+//!     async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Self::Error> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! type Pool = managed::Pool<Manager>;
+//!
+//! async fn answer(obj: Conn<Pool>) -> String {
+//!     obj.to_string()
+//! }
+//!
+//! let pool = Pool::builder(Manager {}).build().unwrap();
+//! let app = App::new()
+//!     .app_data(web::Data::new(pool))
+//!     .route("/answer", web::get().to(answer));
+//! ```
+//!
+//! [`Pool`]: crate::managed::Pool
+//! [`Pool::get()`]: crate::managed::Pool::get
+
+use std::{
+    fmt,
+    future::Future,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+};
+
+use ::actix_web::{
+    Error, FromRequest, HttpRequest, HttpResponse, ResponseError, dev::Payload,
+    error::ErrorInternalServerError, http::StatusCode, web::Data,
+};
+
+use crate::managed::{Manager, Object, Pool, PoolError};
+
+pub use crate::extract::ConnPool;
+
+/// Extractor retrieving an object from a [`Pool`].
+///
+/// The [`Pool`] is looked up in the application data either wrapped in
+/// [`Data`] or registered as is. If neither exists the extractor fails with
+/// `500 Internal Server Error`.
+///
+/// The object is returned to the [`Pool`] once the extractor is dropped.
+/// This usually happens when the handler returns.
+pub struct Conn<P: ConnPool>(pub P::Object);
+
+impl<P: ConnPool> Conn<P> {
+    /// Returns the object retrieved by this extractor.
+    pub fn into_inner(self) -> P::Object {
+        self.0
+    }
+}
+
+impl<P: ConnPool> Deref for Conn<P> {
+    type Target = P::Object;
+
+    fn deref(&self) -> &P::Object {
+        &self.0
+    }
+}
+
+impl<P: ConnPool> DerefMut for Conn<P> {
+    fn deref_mut(&mut self) -> &mut P::Object {
+        &mut self.0
+    }
+}
+
+impl<P> fmt::Debug for Conn<P>
+where
+    P: ConnPool,
+    P::Object: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_tuple("Conn").field(&self.0).finish()
+    }
+}
+
+impl<M, W> FromRequest for Conn<Pool<M, W>>
+where
+    M: Manager + 'static,
+    M::Error: fmt::Debug + fmt::Display + 'static,
+    W: From<Object<M>> + 'static,
+{
+    type Error = Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Error>>>>;
+
+    fn from_request(req: &HttpRequest, _: &mut Payload) -> Self::Future {
+        let pool = req
+            .app_data::<Data<Pool<M, W>>>()
+            .map(|data| Pool::clone(data))
+            .or_else(|| req.app_data::<Pool<M, W>>().cloned());
+        Box::pin(async move {
+            let pool = pool.ok_or_else(|| {
+                ErrorInternalServerError("Pool is not registered as application data")
+            })?;
+            Ok(Conn(pool.get().await?))
+        })
+    }
+}
+
+/// Timeouts, closed pools and full queues result in
+/// `503 Service Unavailable`. All other
+/// errors result in `500 Internal Server Error`.
+///
+/// The response body is empty in order to not leak any details of the
+/// backend to the client.
+impl<E: fmt::Debug + fmt::Display> ResponseError for PoolError<E> {
+    fn status_code(&self) -> StatusCode {
+        match self {
+            Self::Timeout(..) | Self::Closed(_) | Self::QueueFull(_) => {
+                StatusCode::SERVICE_UNAVAILABLE
+            }
+            _ => StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    fn error_response(&self) -> HttpResponse {
+        HttpResponse::new(self.status_code())
+    }
+}
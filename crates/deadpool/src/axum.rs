@@ -0,0 +1,348 @@
+//! Integration with the [`axum`](https://crates.io/crates/axum) web
+//! framework.
+//!
+//! This module provides the [`Conn`] extractor which retrieves an object
+//! from a [`Pool`] stored in the state of the router and the
+//! [`CheckoutLayer`] which limits how long the extractor may wait for an
+//! object.
+//!
+//! # Example
+//!
+//! ```rust,no_run
+//! use std::time::Duration;
+//!
+//! use axum::{Router, routing::get};
+//! use deadpool::{
+//!     axum::{CheckoutLayer, Conn},
+//!     managed::{self, Metrics, RecycleResult},
+//! };
+//!
+//! struct Manager {}
+//!
+//! impl managed::Manager for Manager {
+//!     type Type = usize;
+//!     type Error = ();
+//!
+//!     async fn create(&self) -> Result<usize, ()> {
+//!         Ok(42)
+//!     }
+//!
+//!     async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<()> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! type Pool = managed::Pool<Manager>;
+//!
+//! async fn answer(obj: Conn<Pool>) -> String {
+//!     obj.to_string()
+//! }
+//!
+//! let pool = Pool::builder(Manager {}).build().unwrap();
+//! let app: Router = Router::new()
+//!     .route("/answer", get(answer))
+//!     .layer(CheckoutLayer::new().deadline(Duration::from_secs(1)))
+//!     .with_state(pool);
+//! ```
+//!
+//! [`Pool`]: crate::managed::Pool
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    task::{Context, Poll},
+    time::Duration,
+};
+
+use ::axum::{
+    extract::{FromRef, FromRequestParts},
+    http::{Request, StatusCode, request::Parts},
+    response::{IntoResponse, Response},
+};
+use tokio::time::{Instant, timeout_at};
+use tower_layer::Layer;
+use tower_service::Service;
+
+use crate::managed::{ErrorContext, Manager, Object, Pool, PoolError, TimeoutType};
+
+pub use crate::extract::ConnPool;
+
+/// Extractor retrieving an object from a [`Pool`].
+///
+/// The [`Pool`] is taken from the state of the router via [`FromRef`]. If
+/// a [`CheckoutLayer`] is installed its deadline and [`StatusMapping`] are
+/// used.
+///
+/// The object is returned to the [`Pool`] once the extractor is dropped.
+/// This usually happens when the handler returns. The extractor derefs to
+/// the object, e.g. [`Object<M>`] when using a [`Pool`] without a custom
+/// wrapper type.
+pub struct Conn<P: ConnPool> {
+    obj: P::Object,
+}
+
+impl<P: ConnPool> Conn<P> {
+    /// Returns the object retrieved by this extractor.
+    pub fn into_inner(self) -> P::Object {
+        self.obj
+    }
+}
+
+impl<P: ConnPool> Deref for Conn<P> {
+    type Target = P::Object;
+
+    fn deref(&self) -> &P::Object {
+        &self.obj
+    }
+}
+
+impl<P: ConnPool> DerefMut for Conn<P> {
+    fn deref_mut(&mut self) -> &mut P::Object {
+        &mut self.obj
+    }
+}
+
+impl<P> fmt::Debug for Conn<P>
+where
+    P: ConnPool,
+    P::Object: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Conn").field("obj", &self.obj).finish()
+    }
+}
+
+impl<S, M, W> FromRequestParts<S> for Conn<Pool<M, W>>
+where
+    S: Send + Sync,
+    M: Manager,
+    W: From<Object<M>> + Send,
+    Pool<M, W>: FromRef<S>,
+{
+    type Rejection = PoolRejection<M::Error>;
+
+    async fn from_request_parts(parts: &mut Parts, state: &S) -> Result<Self, Self::Rejection> {
+        let pool = Pool::<M, W>::from_ref(state);
+        let config = parts
+            .extensions
+            .get::<CheckoutConfig>()
+            .copied()
+            .unwrap_or_default();
+        let result = match config.deadline {
+            Some(deadline) => {
+                timeout_at(deadline, pool.get())
+                    .await
+                    .unwrap_or(Err(PoolError::Timeout(
+                        TimeoutType::Total,
+                        ErrorContext::new(),
+                    )))
+            }
+            None => pool.get().await,
+        };
+        let obj = result.map_err(|error| PoolRejection {
+            status: config.status_mapping.status(&error),
+            error,
+        })?;
+        Ok(Conn { obj })
+    }
+}
+
+/// Rejection returned by the [`Conn`] extractor.
+///
+/// It is turned into an empty response using the [`StatusCode`]
+/// determined by the [`StatusMapping`].
+pub struct PoolRejection<E> {
+    error: PoolError<E>,
+    status: StatusCode,
+}
+
+impl<E> PoolRejection<E> {
+    /// Returns the [`PoolError`] which caused this rejection.
+    pub fn error(&self) -> &PoolError<E> {
+        &self.error
+    }
+
+    /// Returns the [`PoolError`] which caused this rejection.
+    pub fn into_error(self) -> PoolError<E> {
+        self.error
+    }
+
+    /// Returns the [`StatusCode`] of the response.
+    pub fn status(&self) -> StatusCode {
+        self.status
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for PoolRejection<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolRejection")
+            .field("error", &self.error)
+            .field("status", &self.status)
+            .finish()
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for PoolRejection<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        self.error.fmt(f)
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for PoolRejection<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        Some(&self.error)
+    }
+}
+
+impl<E> IntoResponse for PoolRejection<E> {
+    fn into_response(self) -> Response {
+        self.status.into_response()
+    }
+}
+
+/// Mapping of [`PoolError`]s to HTTP [`StatusCode`]s.
+#[derive(Clone, Copy, Debug)]
+pub struct StatusMapping {
+    /// Status of [`PoolError::Timeout`].
+    ///
+    /// Default: `503 Service Unavailable`
+    pub timeout: StatusCode,
+
+    /// Status of [`PoolError::Closed`].
+    ///
+    /// Default: `503 Service Unavailable`
+    pub closed: StatusCode,
+
+    /// Status of [`PoolError::QueueFull`].
+    ///
+    /// Default: `503 Service Unavailable`
+    pub queue_full: StatusCode,
+
+    /// Status of [`PoolError::Backend`].
+    ///
+    /// Default: `500 Internal Server Error`
+    pub backend: StatusCode,
+
+    /// Status of all other errors.
+    ///
+    /// Default: `500 Internal Server Error`
+    pub other: StatusCode,
+}
+
+impl StatusMapping {
+    /// Creates the default [`StatusMapping`].
+    #[must_use]
+    pub const fn new() -> Self {
+        Self {
+            timeout: StatusCode::SERVICE_UNAVAILABLE,
+            closed: StatusCode::SERVICE_UNAVAILABLE,
+            queue_full: StatusCode::SERVICE_UNAVAILABLE,
+            backend: StatusCode::INTERNAL_SERVER_ERROR,
+            other: StatusCode::INTERNAL_SERVER_ERROR,
+        }
+    }
+
+    /// Returns the [`StatusCode`] of the given [`PoolError`].
+    pub fn status<E>(&self, error: &PoolError<E>) -> StatusCode {
+        match error {
+            PoolError::Timeout(..) => self.timeout,
+            PoolError::Closed(_) => self.closed,
+            PoolError::QueueFull(_) => self.queue_full,
+            PoolError::Backend(..) => self.backend,
+            _ => self.other,
+        }
+    }
+}
+
+// Implemented manually to provide a custom documentation.
+impl Default for StatusMapping {
+    /// Creates the default [`StatusMapping`] which maps timeouts, closed
+    /// pools and full queues to `503 Service Unavailable` and everything else to
+    /// `500 Internal Server Error`.
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Configuration passed from the [`CheckoutLayer`] to the [`Conn`]
+/// extractor via the request extensions.
+#[derive(Clone, Copy, Debug, Default)]
+struct CheckoutConfig {
+    deadline: Option<Instant>,
+    status_mapping: StatusMapping,
+}
+
+/// [`Layer`] configuring the [`Conn`] extractors of every request.
+///
+/// When a request is received a deadline is computed from the configured
+/// duration. All [`Conn`] extractors of that request fail with
+/// [`TimeoutType::Total`] if they can't retrieve an object before the
+/// deadline.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct CheckoutLayer {
+    deadline: Option<Duration>,
+    status_mapping: StatusMapping,
+}
+
+impl CheckoutLayer {
+    /// Creates a new [`CheckoutLayer`] without a deadline and using the
+    /// default [`StatusMapping`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the maximum duration between receiving a request and
+    /// retrieving an object.
+    #[must_use]
+    pub fn deadline(mut self, value: Duration) -> Self {
+        self.deadline = Some(value);
+        self
+    }
+
+    /// Sets the [`StatusMapping`] used for rejections.
+    #[must_use]
+    pub fn status_mapping(mut self, value: StatusMapping) -> Self {
+        self.status_mapping = value;
+        self
+    }
+}
+
+impl<S> Layer<S> for CheckoutLayer {
+    type Service = CheckoutService<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        CheckoutService {
+            inner,
+            layer: *self,
+        }
+    }
+}
+
+/// [`Service`] created by the [`CheckoutLayer`].
+#[derive(Clone, Copy, Debug)]
+pub struct CheckoutService<S> {
+    inner: S,
+    layer: CheckoutLayer,
+}
+
+impl<S, B> Service<Request<B>> for CheckoutService<S>
+where
+    S: Service<Request<B>>,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = S::Future;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, mut req: Request<B>) -> Self::Future {
+        let _ = req.extensions_mut().insert(CheckoutConfig {
+            deadline: self.layer.deadline.map(|d| Instant::now() + d),
+            status_mapping: self.layer.status_mapping,
+        });
+        self.inner.call(req)
+    }
+}
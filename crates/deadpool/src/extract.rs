@@ -0,0 +1,17 @@
+//! Items shared by the extractors of the web framework integrations.
+
+use crate::managed::{Manager, Object, Pool};
+
+/// Pool types which can be used with the `Conn` extractors.
+///
+/// This trait is implemented for every [`Pool`] and makes it possible to
+/// name the object type of a [`Pool`] type alias, e.g.
+/// `Conn<deadpool_postgres::Pool>`.
+pub trait ConnPool {
+    /// Type of the objects retrieved from the [`Pool`].
+    type Object;
+}
+
+impl<M: Manager, W: From<Object<M>>> ConnPool for Pool<M, W> {
+    type Object = W;
+}
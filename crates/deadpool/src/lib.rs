@@ -29,6 +29,25 @@ pub mod managed;
 #[cfg_attr(docsrs, doc(cfg(feature = "unmanaged")))]
 pub mod unmanaged;
 
+#[cfg(feature = "testing")]
+#[cfg_attr(docsrs, doc(cfg(feature = "testing")))]
+pub mod testing;
+
+#[cfg(feature = "axum")]
+#[cfg_attr(docsrs, doc(cfg(feature = "axum")))]
+pub mod axum;
+
+#[cfg(feature = "actix-web")]
+#[cfg_attr(docsrs, doc(cfg(feature = "actix-web")))]
+pub mod actix_web;
+
+#[cfg(feature = "r2d2")]
+#[cfg_attr(docsrs, doc(cfg(feature = "r2d2")))]
+pub mod r2d2;
+
+#[cfg(any(feature = "axum", feature = "actix-web"))]
+mod extract;
+
 pub use deadpool_runtime::Runtime;
 
 /// The current pool status.
@@ -40,7 +59,7 @@ pub use deadpool_runtime::Runtime;
 /// are meant for an overall insight.
 ///
 /// [1]: https://en.wikipedia.org/wiki/Eventual_consistency
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
 pub struct Status {
     /// The maximum size of the pool.
     pub max_size: usize,
@@ -0,0 +1,292 @@
+//! [`Manager`] spreading objects across multiple endpoints.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use super::{Extensions, Manager, Metrics, RecycleResult, metrics::Instant};
+
+/// Strategy used by a [`BalancedManager`] for choosing the endpoint of a
+/// new object.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum BalanceStrategy {
+    /// Use the endpoints one after another.
+    #[default]
+    RoundRobin,
+
+    /// Use the endpoint with the fewest objects.
+    LeastObjects,
+}
+
+/// [`Manager`] spreading the creation of objects across multiple wrapped
+/// [`Manager`]s, one per endpoint.
+///
+/// An endpoint failing to create an object is ejected for the
+/// [`eject_duration`](Self::eject_duration) and the next endpoint is tried
+/// instead. Ejected endpoints are only used if all endpoints are ejected.
+///
+/// Every object remembers the endpoint which created it so recycling and
+/// detaching is always done by its [`Manager`].
+pub struct BalancedManager<M> {
+    endpoints: Vec<Endpoint<M>>,
+    strategy: BalanceStrategy,
+    eject_duration: Duration,
+    next: AtomicUsize,
+}
+
+struct Endpoint<M> {
+    manager: M,
+    objects: Arc<AtomicUsize>,
+    failed_at: Mutex<Option<Instant>>,
+}
+
+impl<M: Manager> BalancedManager<M> {
+    /// Creates a new [`BalancedManager`] using the given `managers` as
+    /// endpoints.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `managers` is empty.
+    #[must_use]
+    pub fn new(managers: impl IntoIterator<Item = M>) -> Self {
+        let endpoints: Vec<_> = managers
+            .into_iter()
+            .map(|manager| Endpoint {
+                manager,
+                objects: Arc::new(AtomicUsize::new(0)),
+                failed_at: Mutex::new(None),
+            })
+            .collect();
+        assert!(
+            !endpoints.is_empty(),
+            "BalancedManager requires at least one manager"
+        );
+        Self {
+            endpoints,
+            strategy: BalanceStrategy::default(),
+            eject_duration: Duration::from_secs(10),
+            next: AtomicUsize::new(0),
+        }
+    }
+
+    /// Sets the [`BalanceStrategy`].
+    ///
+    /// Default: [`BalanceStrategy::RoundRobin`]
+    #[must_use]
+    pub fn strategy(mut self, value: BalanceStrategy) -> Self {
+        self.strategy = value;
+        self
+    }
+
+    /// Sets the duration for which an endpoint is ejected after failing to
+    /// create an object.
+    ///
+    /// Default: 10 seconds
+    #[must_use]
+    pub fn eject_duration(mut self, value: Duration) -> Self {
+        self.eject_duration = value;
+        self
+    }
+
+    /// Returns the number of endpoints.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.endpoints.len()
+    }
+
+    /// Always returns `false` as a [`BalancedManager`] can't be empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        false
+    }
+
+    /// Returns the [`Manager`] of the endpoint at the given `index`.
+    #[must_use]
+    pub fn manager(&self, index: usize) -> Option<&M> {
+        self.endpoints.get(index).map(|endpoint| &endpoint.manager)
+    }
+
+    /// Returns the number of objects created by the endpoint at the given
+    /// `index` which were neither dropped nor detached, yet.
+    #[must_use]
+    pub fn objects(&self, index: usize) -> Option<usize> {
+        self.endpoints
+            .get(index)
+            .map(|endpoint| endpoint.objects.load(Ordering::Relaxed))
+    }
+
+    /// Checks whether the endpoint at the given `index` is currently
+    /// ejected.
+    #[must_use]
+    pub fn is_ejected(&self, index: usize) -> bool {
+        self.endpoints
+            .get(index)
+            .is_some_and(|endpoint| self.ejected(endpoint))
+    }
+
+    fn ejected(&self, endpoint: &Endpoint<M>) -> bool {
+        endpoint
+            .failed_at
+            .lock()
+            .unwrap()
+            .is_some_and(|t| t.elapsed() < self.eject_duration)
+    }
+
+    /// Returns the indices of the endpoints in the order they should be
+    /// tried for creating a new object.
+    fn candidates(&self) -> Vec<usize> {
+        let len = self.endpoints.len();
+        let mut candidates: Vec<usize> = match self.strategy {
+            BalanceStrategy::RoundRobin => {
+                let start = self.next.fetch_add(1, Ordering::Relaxed);
+                (0..len).map(|i| (start + i) % len).collect()
+            }
+            BalanceStrategy::LeastObjects => {
+                let mut candidates: Vec<usize> = (0..len).collect();
+                candidates.sort_by_key(|&i| self.endpoints[i].objects.load(Ordering::Relaxed));
+                candidates
+            }
+        };
+        // Ejected endpoints are tried last.
+        candidates.sort_by_key(|&i| self.ejected(&self.endpoints[i]));
+        candidates
+    }
+}
+
+impl<M: Manager> Manager for BalancedManager<M> {
+    type Type = BalancedObject<M::Type>;
+    type Error = M::Error;
+
+    async fn create(&self) -> Result<Self::Type, Self::Error> {
+        let candidates = self.candidates();
+        let healthy = candidates
+            .iter()
+            .filter(|&&i| !self.ejected(&self.endpoints[i]))
+            .count();
+        let tries = if healthy == 0 {
+            candidates.len()
+        } else {
+            healthy
+        };
+        let mut last_error = None;
+        for index in candidates.into_iter().take(tries) {
+            let endpoint = &self.endpoints[index];
+            match endpoint.manager.create().await {
+                Ok(obj) => {
+                    *endpoint.failed_at.lock().unwrap() = None;
+                    let _ = endpoint.objects.fetch_add(1, Ordering::Relaxed);
+                    return Ok(BalancedObject {
+                        obj,
+                        index,
+                        objects: Some(endpoint.objects.clone()),
+                    });
+                }
+                Err(e) => {
+                    *endpoint.failed_at.lock().unwrap() = Some(Instant::now());
+                    last_error = Some(e);
+                }
+            }
+        }
+        // At least one endpoint is always tried.
+        Err(last_error.unwrap())
+    }
+
+    async fn recycle(&self, obj: &mut Self::Type, metrics: &Metrics) -> RecycleResult<Self::Error> {
+        self.endpoints[obj.index]
+            .manager
+            .recycle(&mut obj.obj, metrics)
+            .await
+    }
+
+    async fn recycle_with_extensions(
+        &self,
+        obj: &mut Self::Type,
+        metrics: &Metrics,
+        extensions: &mut Extensions,
+    ) -> RecycleResult<Self::Error> {
+        self.endpoints[obj.index]
+            .manager
+            .recycle_with_extensions(&mut obj.obj, metrics, extensions)
+            .await
+    }
+
+    fn detach(&self, obj: &mut Self::Type) {
+        self.endpoints[obj.index].manager.detach(&mut obj.obj);
+        obj.release();
+    }
+
+    fn is_connection_error(&self, error: &Self::Error) -> bool {
+        self.endpoints[0].manager.is_connection_error(error)
+    }
+}
+
+// Implemented manually as the wrapped managers don't need to implement
+// `Debug`.
+impl<M> fmt::Debug for BalancedManager<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BalancedManager")
+            .field("endpoints", &self.endpoints.len())
+            .field("strategy", &self.strategy)
+            .field("eject_duration", &self.eject_duration)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Object created by a [`BalancedManager`].
+///
+/// It derefs to the object created by the wrapped [`Manager`].
+pub struct BalancedObject<T> {
+    obj: T,
+    index: usize,
+    objects: Option<Arc<AtomicUsize>>,
+}
+
+impl<T> BalancedObject<T> {
+    /// Returns the index of the endpoint which created this object.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Removes this object from the object count of its endpoint.
+    fn release(&mut self) {
+        if let Some(objects) = self.objects.take() {
+            let _ = objects.fetch_sub(1, Ordering::Relaxed);
+        }
+    }
+}
+
+impl<T> Drop for BalancedObject<T> {
+    fn drop(&mut self) {
+        self.release();
+    }
+}
+
+impl<T> Deref for BalancedObject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.obj
+    }
+}
+
+impl<T> DerefMut for BalancedObject<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.obj
+    }
+}
+
+impl<T: fmt::Debug> fmt::Debug for BalancedObject<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BalancedObject")
+            .field("obj", &self.obj)
+            .field("index", &self.index)
+            .finish()
+    }
+}
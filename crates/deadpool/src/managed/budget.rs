@@ -0,0 +1,177 @@
+//! Limit of live objects shared by multiple pools.
+
+use std::{
+    fmt,
+    sync::{
+        Arc, Mutex, Weak,
+        atomic::{AtomicUsize, Ordering},
+    },
+};
+
+use tokio::sync::Semaphore;
+
+use super::dropguard::DropGuard;
+
+/// Limit of the total number of live objects shared by multiple [`Pool`]s.
+///
+/// This is useful if several [`Pool`]s, possibly using different
+/// [`Manager`] types, connect to the same server which only accepts a
+/// limited number of connections. A [`Budget`] is attached to a [`Pool`]
+/// via [`PoolBuilder::budget()`] and can be cloned cheaply.
+///
+/// If a [`Pool`] needs to create an object while the [`Budget`] is
+/// exhausted an idle object of the [`Pool`] holding the most idle objects
+/// is discarded with [`DiscardReason::Reclaimed`]. If there are no idle
+/// objects the [`Pool`] waits for an object to be discarded or returned
+/// to any of the [`Pool`]s. Objects returned to a [`Pool`] without waiting
+/// users are discarded instead of becoming idle while other [`Pool`]s are
+/// waiting for the [`Budget`]. Waiting for the [`Budget`] is limited by
+/// [`Timeouts::wait`] and fails with [`TimeoutType::Budget`].
+///
+/// The maximum is advisory for objects leaving their [`Pool`]: Objects
+/// taken via [`Object::take()`] release their share of the [`Budget`] as
+/// they are no longer tracked by their [`Pool`], so callers taking objects
+/// can end up with more live objects than the maximum. Connections which
+/// are kept open after being taken must be accounted for when choosing
+/// the maximum.
+///
+/// [`DiscardReason::Reclaimed`]: super::DiscardReason::Reclaimed
+/// [`Manager`]: super::Manager
+/// [`Object::take()`]: super::Object::take
+/// [`Pool`]: super::Pool
+/// [`PoolBuilder::budget()`]: super::PoolBuilder::budget
+/// [`TimeoutType::Budget`]: super::TimeoutType::Budget
+/// [`Timeouts::wait`]: super::Timeouts::wait
+#[derive(Clone)]
+pub struct Budget {
+    inner: Arc<BudgetInner>,
+}
+
+struct BudgetInner {
+    max: usize,
+    /// Every live object holds one permit of this semaphore.
+    semaphore: Semaphore,
+    /// Number of pools waiting for a permit.
+    waiting: AtomicUsize,
+    pools: Mutex<Vec<Weak<dyn Reclaim>>>,
+}
+
+/// Pool which can give up idle objects in favor of other pools sharing the
+/// same [`Budget`].
+pub(crate) trait Reclaim: Send + Sync {
+    /// Returns the number of idle objects.
+    fn idle(&self) -> usize;
+
+    /// Discards the least recently used idle object and returns whether
+    /// there was one.
+    fn reclaim_idle(&self) -> bool;
+}
+
+impl Budget {
+    /// Creates a new [`Budget`] allowing at most `max` live objects.
+    #[must_use]
+    pub fn new(max: usize) -> Self {
+        Self {
+            inner: Arc::new(BudgetInner {
+                max,
+                semaphore: Semaphore::new(max),
+                waiting: AtomicUsize::new(0),
+                pools: Mutex::new(Vec::new()),
+            }),
+        }
+    }
+
+    /// Returns the maximum number of live objects.
+    #[must_use]
+    pub fn max(&self) -> usize {
+        self.inner.max
+    }
+
+    /// Returns the number of live objects of all [`Pool`]s using this
+    /// [`Budget`].
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn live(&self) -> usize {
+        self.inner.max - self.inner.semaphore.available_permits()
+    }
+
+    pub(crate) fn register(&self, pool: Weak<dyn Reclaim>) {
+        let mut pools = self.inner.pools.lock().unwrap();
+        pools.retain(|pool| pool.strong_count() > 0);
+        pools.push(pool);
+    }
+
+    /// Checks whether any pool is waiting for a permit.
+    pub(crate) fn has_waiters(&self) -> bool {
+        self.inner.waiting.load(Ordering::Relaxed) > 0
+    }
+
+    /// Acquires a permit for a new object reclaiming an idle object from
+    /// another pool or waiting for one if this [`Budget`] is exhausted.
+    pub(crate) async fn acquire(&self) -> BudgetPermit {
+        let inner = &self.inner;
+        let permit = match inner.semaphore.try_acquire() {
+            Ok(permit) => permit,
+            Err(_) => {
+                // Reclaiming the object releases its permit. The reclaimed
+                // permit might be taken by a concurrent caller in which case
+                // this one waits.
+                let _ = self.reclaim_idle();
+                let _ = inner.waiting.fetch_add(1, Ordering::Relaxed);
+                let _waiting_guard = DropGuard(|| {
+                    let _ = inner.waiting.fetch_sub(1, Ordering::Relaxed);
+                });
+                // The semaphore is never closed.
+                inner.semaphore.acquire().await.unwrap()
+            }
+        };
+        permit.forget();
+        BudgetPermit {
+            budget: inner.clone(),
+        }
+    }
+
+    /// Discards an idle object of the pool holding the most idle objects.
+    fn reclaim_idle(&self) -> bool {
+        let pools: Vec<_> = self
+            .inner
+            .pools
+            .lock()
+            .unwrap()
+            .iter()
+            .filter_map(Weak::upgrade)
+            .collect();
+        pools
+            .iter()
+            .filter(|pool| pool.idle() > 0)
+            .max_by_key(|pool| pool.idle())
+            .is_some_and(|pool| pool.reclaim_idle())
+    }
+}
+
+impl fmt::Debug for Budget {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Budget")
+            .field("max", &self.max())
+            .field("live", &self.live())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Permit of a live object which is released when the object is dropped.
+pub(crate) struct BudgetPermit {
+    budget: Arc<BudgetInner>,
+}
+
+impl Drop for BudgetPermit {
+    fn drop(&mut self) {
+        self.budget.semaphore.add_permits(1);
+    }
+}
+
+impl fmt::Debug for BudgetPermit {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("BudgetPermit").finish_non_exhaustive()
+    }
+}
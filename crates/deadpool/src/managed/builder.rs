@@ -1,10 +1,18 @@
-use std::{fmt, marker::PhantomData, time::Duration};
+use std::{
+    fmt,
+    marker::PhantomData,
+    sync::{Arc, Weak},
+    time::Duration,
+};
 
 use crate::Runtime;
 
 use super::{
-    Manager, Object, Pool, PoolConfig, QueueMode, Timeouts,
+    Budget, DiscardReason, Manager, Metrics, Object, ObjectId, Pool, PoolConfig, PoolError,
+    QueueMode, Timeouts,
+    budget::Reclaim,
     hooks::{Hook, Hooks},
+    pool::PoolInner,
 };
 
 /// Possible errors returned when [`PoolBuilder::build()`] fails to build a
@@ -13,6 +21,10 @@ use super::{
 pub enum BuildError {
     /// [`Runtime`] is required due to configured timeouts.
     NoRuntimeSpecified,
+
+    /// [`PoolConfig::max_uses`] is set to `0` which would discard every
+    /// object without it ever being used.
+    InvalidMaxUses,
 }
 
 impl fmt::Display for BuildError {
@@ -22,6 +34,10 @@ impl fmt::Display for BuildError {
                 f,
                 "Error occurred while building the pool: Timeouts require a runtime",
             ),
+            Self::InvalidMaxUses => write!(
+                f,
+                "Error occurred while building the pool: max_uses must be greater than 0",
+            ),
         }
     }
 }
@@ -29,7 +45,36 @@ impl fmt::Display for BuildError {
 impl std::error::Error for BuildError {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::NoRuntimeSpecified => None,
+            Self::NoRuntimeSpecified | Self::InvalidMaxUses => None,
+        }
+    }
+}
+
+/// Possible errors returned by [`PoolBuilder::build_and_warm()`].
+#[derive(Debug)]
+pub enum WarmUpError<E> {
+    /// The [`Pool`] could not be built.
+    Build(BuildError),
+
+    /// The [`Pool`] could not be warmed up, e.g. because the [`Manager`]
+    /// failed to create an object.
+    Pool(PoolError<E>),
+}
+
+impl<E: fmt::Display> fmt::Display for WarmUpError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Build(e) => write!(f, "{}", e),
+            Self::Pool(e) => write!(f, "Error occurred while warming up the pool: {}", e),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for WarmUpError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Build(e) => Some(e),
+            Self::Pool(e) => Some(e),
         }
     }
 }
@@ -47,9 +92,13 @@ where
     pub(crate) config: PoolConfig,
     pub(crate) runtime: Option<Runtime>,
     pub(crate) hooks: Hooks<M>,
+    pub(crate) budget: Option<(Budget, RegisterFn<M>)>,
     _wrapper: PhantomData<fn() -> W>,
 }
 
+/// Function registering a [`Pool`] at its [`Budget`].
+pub(crate) type RegisterFn<M> = fn(&Arc<PoolInner<M>>) -> Weak<dyn Reclaim>;
+
 // Implemented manually to avoid unnecessary trait bound on `W` type parameter.
 impl<M, W> fmt::Debug for PoolBuilder<M, W>
 where
@@ -62,6 +111,7 @@ where
             .field("config", &self.config)
             .field("runtime", &self.runtime)
             .field("hooks", &self.hooks)
+            .field("budget", &self.budget.as_ref().map(|(budget, _)| budget))
             .field("_wrapper", &self._wrapper)
             .finish()
     }
@@ -78,6 +128,7 @@ where
             config: PoolConfig::default(),
             runtime: None,
             hooks: Hooks::default(),
+            budget: None,
             _wrapper: PhantomData,
         }
     }
@@ -90,13 +141,34 @@ where
     pub fn build(self) -> Result<Pool<M, W>, BuildError> {
         // Return an error if a timeout is configured without runtime.
         let t = &self.config.timeouts;
-        if (t.wait.is_some() || t.create.is_some() || t.recycle.is_some()) && self.runtime.is_none()
+        if (t.wait.is_some() || t.create.is_some() || t.recycle.is_some() || t.total.is_some())
+            && self.runtime.is_none()
         {
             return Err(BuildError::NoRuntimeSpecified);
         }
+        if self.config.max_uses == Some(0) {
+            return Err(BuildError::InvalidMaxUses);
+        }
         Ok(Pool::from_builder(self))
     }
 
+    /// Builds the [`Pool`] and creates `n` objects concurrently before
+    /// returning it.
+    ///
+    /// This makes it possible to detect configuration errors like a wrong
+    /// password at startup rather than on the first use of the [`Pool`].
+    /// See [`Pool::warm_up()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// See [`WarmUpError`] for details. The first error returned while
+    /// creating the objects fails the whole build.
+    pub async fn build_and_warm(self, n: usize) -> Result<Pool<M, W>, WarmUpError<M::Error>> {
+        let pool = self.build().map_err(WarmUpError::Build)?;
+        pool.warm_up(n).await.map_err(WarmUpError::Pool)?;
+        Ok(pool)
+    }
+
     /// Sets a [`PoolConfig`] to build the [`Pool`] with.
     pub fn config(mut self, value: PoolConfig) -> Self {
         self.config = value;
@@ -133,6 +205,40 @@ where
         self
     }
 
+    /// Sets the [`Timeouts::total`] value of the [`PoolConfig::timeouts`].
+    pub fn total_timeout(mut self, value: Option<Duration>) -> Self {
+        self.config.timeouts.total = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_uses`].
+    pub fn max_uses(mut self, value: Option<usize>) -> Self {
+        self.config.max_uses = value;
+        self
+    }
+
+    /// Sets the [`PoolConfig::recycle_check_interval`].
+    pub fn recycle_check_interval(mut self, value: Option<Duration>) -> Self {
+        self.config.recycle_check_interval = value;
+        self
+    }
+
+    /// Attaches the [`Pool`] to the given [`Budget`] limiting the number of
+    /// live objects shared with other [`Pool`]s.
+    pub fn budget(mut self, value: Budget) -> Self
+    where
+        M: 'static,
+    {
+        self.budget = Some((value, |inner| Arc::downgrade(inner) as Weak<dyn Reclaim>));
+        self
+    }
+
+    /// Sets the [`PoolConfig::max_waiting`].
+    pub fn max_waiting(mut self, value: Option<usize>) -> Self {
+        self.config.max_waiting = value;
+        self
+    }
+
     /// Sets the [`PoolConfig::queue_mode`].
     pub fn queue_mode(mut self, value: QueueMode) -> Self {
         self.config.queue_mode = value;
@@ -166,6 +272,30 @@ where
         self
     }
 
+    /// Attaches a `pre_return` hook.
+    ///
+    /// The given `hook` will be called each time an [`Object`] is released
+    /// via [`Object::release()`] right before it is returned to the
+    /// [`Pool`]. It is not called when an [`Object`] is simply dropped.
+    pub fn pre_return(mut self, hook: impl Into<Hook<M>>) -> Self {
+        self.hooks.pre_return.push(hook.into());
+        self
+    }
+
+    /// Attaches an `on_discard` callback.
+    ///
+    /// The given callback will be called each time an [`Object`] is
+    /// discarded by the [`Pool`] together with the [`DiscardReason`]. As it
+    /// is called from within [`Pool::get()`] and when dropping an [`Object`]
+    /// it must not block.
+    pub fn on_discard(
+        mut self,
+        f: impl Fn(ObjectId, &Metrics, DiscardReason) + Sync + Send + 'static,
+    ) -> Self {
+        self.hooks.on_discard.push(Box::new(f));
+        self
+    }
+
     /// Sets the [`Runtime`].
     ///
     /// # Important
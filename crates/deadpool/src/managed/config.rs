@@ -32,6 +32,69 @@ pub struct PoolConfig {
     /// [`Pool`]: super::Pool
     #[cfg_attr(feature = "serde", serde(default))]
     pub queue_mode: QueueMode,
+
+    /// Maximum number of times an object is checked out from the [`Pool`].
+    ///
+    /// Once an object has been used this many times it is discarded when
+    /// being returned to the [`Pool`] and a new object is created in its
+    /// place. This is useful for backends which leak memory per session.
+    /// A value of `0` is rejected by [`PoolBuilder::build()`].
+    ///
+    /// Every time an object is handed out, e.g. by [`Pool::get()`],
+    /// [`Pool::run()`] or [`Reservation::get()`], counts as a use. Objects
+    /// created by [`Pool::warm_up()`] have not been used yet.
+    ///
+    /// Default: No limit
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`Pool::run()`]: super::Pool::run
+    /// [`Pool::warm_up()`]: super::Pool::warm_up
+    /// [`PoolBuilder::build()`]: super::PoolBuilder::build
+    /// [`Reservation::get()`]: super::Reservation::get
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_uses: Option<usize>,
+
+    /// Interval in which objects are handed out without calling
+    /// [`Manager::recycle()`].
+    ///
+    /// If an object was created, recycled or used successfully via
+    /// [`Pool::run()`] within this interval it is considered healthy and
+    /// the check is skipped. The `pre_recycle` and `post_recycle` hooks
+    /// are still run. This avoids a round-trip per checkout for managers
+    /// which verify their objects when being recycled.
+    ///
+    /// **Caution:** The whole recycle step is skipped, not just the health
+    /// check. Neither [`Manager::recycle()`] nor
+    /// [`Manager::recycle_with_extensions()`] is called, so any state the
+    /// manager resets while recycling is carried over to the next user.
+    /// E.g. `deadpool-postgres` won't run the statements of
+    /// `RecyclingMethod::Clean` for such objects. Only enable this if the
+    /// objects don't need to be reset between checkouts.
+    ///
+    /// Default: No interval, i.e. every object is checked
+    ///
+    /// [`Manager::recycle()`]: super::Manager::recycle
+    /// [`Manager::recycle_with_extensions()`]: super::Manager::recycle_with_extensions
+    /// [`Pool::run()`]: super::Pool::run
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub recycle_check_interval: Option<Duration>,
+
+    /// Maximum number of futures waiting for an object.
+    ///
+    /// Once this many futures are waiting [`Pool::get()`] fails with
+    /// [`PoolError::QueueFull`] immediately instead of waiting. This allows
+    /// shedding load quickly rather than queuing futures which would time
+    /// out anyway. Just like [`Status::waiting`] this limit is not exact
+    /// under heavy load.
+    ///
+    /// Default: No limit
+    ///
+    /// [`Pool::get()`]: super::Pool::get
+    /// [`PoolError::QueueFull`]: super::PoolError::QueueFull
+    /// [`Status::waiting`]: crate::Status::waiting
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub max_waiting: Option<usize>,
 }
 
 impl PoolConfig {
@@ -43,6 +106,9 @@ impl PoolConfig {
             max_size,
             timeouts: Timeouts::default(),
             queue_mode: QueueMode::default(),
+            max_uses: None,
+            recycle_check_interval: None,
+            max_waiting: None,
         }
     }
 }
@@ -70,6 +136,11 @@ pub struct Timeouts {
 
     /// Timeout when recycling an object.
     pub recycle: Option<Duration>,
+
+    /// Timeout for the whole checkout including waiting for a slot,
+    /// creating and recycling objects. This also covers recycling several
+    /// objects in a row when some of them fail to be recycled.
+    pub total: Option<Duration>,
 }
 
 impl Timeouts {
@@ -80,6 +151,7 @@ impl Timeouts {
             create: None,
             wait: None,
             recycle: None,
+            total: None,
         }
     }
 
@@ -91,6 +163,7 @@ impl Timeouts {
             create: None,
             wait: Some(Duration::from_millis(wait)),
             recycle: None,
+            total: None,
         }
     }
 }
@@ -103,6 +176,65 @@ impl Default for Timeouts {
     }
 }
 
+/// Policy for retrying operations passed to [`Pool::run()`].
+///
+/// An attempt is retried if it fails with an error which the [`Manager`]
+/// classifies as a connection error via [`Manager::is_connection_error()`].
+///
+/// **Caution:** Retrying is only safe for idempotent operations. A
+/// connection might break after the server already applied the operation
+/// in which case it is applied twice. Use [`RetryPolicy::never()`] for all
+/// other operations.
+///
+/// [`Manager`]: super::Manager
+/// [`Manager::is_connection_error()`]: super::Manager::is_connection_error
+/// [`Pool::run()`]: super::Pool::run
+#[derive(Clone, Copy, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Deserialize, serde::Serialize))]
+pub struct RetryPolicy {
+    /// Maximum number of attempts including the first one.
+    ///
+    /// Default: `3`
+    pub max_attempts: usize,
+
+    /// Delay between two attempts.
+    ///
+    /// **Important:** Setting a delay requires a [`Runtime`] to be
+    /// specified.
+    ///
+    /// Default: No delay
+    ///
+    /// [`Runtime`]: crate::Runtime
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub delay: Option<Duration>,
+}
+
+impl RetryPolicy {
+    /// Creates a new [`RetryPolicy`] with the given maximum number of
+    /// attempts and no delay.
+    #[must_use]
+    pub const fn new(max_attempts: usize) -> Self {
+        Self {
+            max_attempts,
+            delay: None,
+        }
+    }
+
+    /// Creates a new [`RetryPolicy`] which never retries.
+    #[must_use]
+    pub const fn never() -> Self {
+        Self::new(1)
+    }
+}
+
+// Implemented manually to provide a custom documentation.
+impl Default for RetryPolicy {
+    /// Creates a [`RetryPolicy`] with up to `3` attempts and no delay.
+    fn default() -> Self {
+        Self::new(3)
+    }
+}
+
 /// Mode for dequeuing [`Object`]s from a [`Pool`].
 ///
 /// [`Object`]: super::Object
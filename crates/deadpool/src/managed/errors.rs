@@ -1,6 +1,7 @@
-use std::{borrow::Cow, fmt};
+use std::{borrow::Cow, fmt, time::Duration};
 
-use super::hooks::HookError;
+use super::hooks::{DiscardReason, HookError};
+use crate::Status;
 
 /// Possible errors returned by the [`Manager::recycle()`] method.
 ///
@@ -51,6 +52,7 @@ impl<E: std::error::Error + 'static> std::error::Error for RecycleError<E> {
 ///
 /// [`Pool::get()`]: super::Pool::get
 #[derive(Clone, Copy, Debug)]
+#[non_exhaustive]
 pub enum TimeoutType {
     /// Timeout happened while waiting for a slot to become available.
     Wait,
@@ -60,64 +62,312 @@ pub enum TimeoutType {
 
     /// Timeout happened while recycling an object.
     Recycle,
+
+    /// Timeout happened while waiting for the [`Budget`] to allow creating
+    /// a new object.
+    ///
+    /// [`Budget`]: super::Budget
+    Budget,
+
+    /// The [`Timeouts::total`] or the deadline passed to
+    /// [`Pool::get_until()`] was exceeded.
+    ///
+    /// [`Pool::get_until()`]: super::Pool::get_until
+    /// [`Timeouts::total`]: super::Timeouts::total
+    Total,
 }
 
 /// Possible errors returned by [`Pool::get()`] method.
 ///
+/// Every variant carries an [`ErrorContext`] describing the state of the
+/// [`Pool`] at the time of the failure. It is empty for errors which were
+/// not returned by a [`Pool`] itself, e.g. when converting a backend error
+/// via [`From`].
+///
+/// [`Pool`]: super::Pool
 /// [`Pool::get()`]: super::Pool::get
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum PoolError<E> {
     /// Timeout happened.
-    Timeout(TimeoutType),
+    Timeout(TimeoutType, ErrorContext<E>),
 
     /// Backend reported an error.
-    Backend(E),
+    Backend(E, ErrorContext<E>),
+
+    /// The operation passed to [`Pool::run()`] or [`Pool::run_with_retry()`]
+    /// failed.
+    ///
+    /// [`Pool::run()`]: super::Pool::run
+    /// [`Pool::run_with_retry()`]: super::Pool::run_with_retry
+    Operation(E, ErrorContext<E>),
 
     /// [`Pool`] has been closed.
     ///
     /// [`Pool`]: super::Pool
-    Closed,
+    Closed(ErrorContext<E>),
 
     /// No [`Runtime`] was specified.
     ///
     /// [`Runtime`]: crate::Runtime
-    NoRuntimeSpecified,
+    NoRuntimeSpecified(ErrorContext<E>),
 
     /// A `post_create` hook reported an error.
-    PostCreateHook(HookError<E>),
+    PostCreateHook(HookError<E>, ErrorContext<E>),
+
+    /// [`PoolConfig::max_waiting`] futures are already waiting for an
+    /// object.
+    ///
+    /// [`PoolConfig::max_waiting`]: super::PoolConfig::max_waiting
+    QueueFull(ErrorContext<E>),
+
+    /// The current task already holds an object of the exhausted [`Pool`]
+    /// and would possibly wait forever for another one.
+    ///
+    /// This is only detected within [`track_checkouts()`].
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`track_checkouts()`]: super::track_checkouts
+    NestedCheckout(ErrorContext<E>),
+}
+
+impl<E> PoolError<E> {
+    /// Returns the [`ErrorContext`] of this error.
+    pub fn context(&self) -> &ErrorContext<E> {
+        match self {
+            Self::Timeout(_, ctx)
+            | Self::Backend(_, ctx)
+            | Self::Operation(_, ctx)
+            | Self::Closed(ctx)
+            | Self::NoRuntimeSpecified(ctx)
+            | Self::PostCreateHook(_, ctx)
+            | Self::QueueFull(ctx)
+            | Self::NestedCheckout(ctx) => ctx,
+        }
+    }
+
+    /// Returns the [`ErrorContext`] of this error mutably.
+    pub(crate) fn context_mut(&mut self) -> &mut ErrorContext<E> {
+        match self {
+            Self::Timeout(_, ctx)
+            | Self::Backend(_, ctx)
+            | Self::Operation(_, ctx)
+            | Self::Closed(ctx)
+            | Self::NoRuntimeSpecified(ctx)
+            | Self::PostCreateHook(_, ctx)
+            | Self::QueueFull(ctx)
+            | Self::NestedCheckout(ctx) => ctx,
+        }
+    }
 }
 
 impl<E> From<E> for PoolError<E> {
     fn from(e: E) -> Self {
-        Self::Backend(e)
+        Self::Backend(e, ErrorContext::new())
     }
 }
 
 impl<E: fmt::Display> fmt::Display for PoolError<E> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
-            Self::Timeout(tt) => match tt {
+            Self::Timeout(tt, _) => match tt {
                 TimeoutType::Wait => write!(
                     f,
                     "Timeout occurred while waiting for a slot to become available"
                 ),
                 TimeoutType::Create => write!(f, "Timeout occurred while creating a new object"),
                 TimeoutType::Recycle => write!(f, "Timeout occurred while recycling an object"),
+                TimeoutType::Budget => write!(
+                    f,
+                    "Timeout occurred while waiting for the budget to allow a new object"
+                ),
+                TimeoutType::Total => write!(f, "Timeout occurred while retrieving an object"),
             },
-            Self::Backend(e) => write!(f, "Error occurred while creating a new object: {}", e),
-            Self::Closed => write!(f, "Pool has been closed"),
-            Self::NoRuntimeSpecified => write!(f, "No runtime specified"),
-            Self::PostCreateHook(e) => writeln!(f, "`post_create` hook failed: {}", e),
+            Self::Backend(e, _) => write!(f, "Error occurred while creating a new object: {}", e),
+            Self::Operation(e, _) => write!(f, "Error occurred while running an operation: {}", e),
+            Self::Closed(_) => write!(f, "Pool has been closed"),
+            Self::NoRuntimeSpecified(_) => write!(f, "No runtime specified"),
+            Self::PostCreateHook(e, _) => write!(f, "`post_create` hook failed: {}", e),
+            Self::QueueFull(_) => write!(f, "Too many futures are waiting for an object"),
+            Self::NestedCheckout(_) => write!(
+                f,
+                "The current task already holds an object of the exhausted pool"
+            ),
+        }?;
+        let ctx = self.context();
+        if !ctx.is_empty() {
+            write!(f, " ({})", ctx)?;
         }
+        Ok(())
     }
 }
 
 impl<E: std::error::Error + 'static> std::error::Error for PoolError<E> {
     fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match self {
-            Self::Timeout(_) | Self::Closed | Self::NoRuntimeSpecified => None,
-            Self::Backend(e) => Some(e),
-            Self::PostCreateHook(e) => Some(e),
+            Self::Timeout(..)
+            | Self::Closed(_)
+            | Self::NoRuntimeSpecified(_)
+            | Self::QueueFull(_)
+            | Self::NestedCheckout(_) => None,
+            Self::Backend(e, _) | Self::Operation(e, _) => Some(e),
+            Self::PostCreateHook(e, _) => Some(e),
+        }
+    }
+}
+
+/// Context of a [`PoolError`] describing what happened while trying to
+/// retrieve an object.
+pub struct ErrorContext<E> {
+    inner: Option<Box<ErrorContextInner<E>>>,
+}
+
+#[derive(Debug)]
+struct ErrorContextInner<E> {
+    elapsed: Duration,
+    status: Status,
+    discarded: usize,
+    last_discard_reason: Option<DiscardReason>,
+    last_recycle_error: Option<RecycleError<E>>,
+    attempts: usize,
+}
+
+impl<E> ErrorContext<E> {
+    /// Creates an empty [`ErrorContext`].
+    #[must_use]
+    pub fn new() -> Self {
+        Self { inner: None }
+    }
+
+    pub(crate) fn from_checkout(
+        elapsed: Duration,
+        status: Status,
+        discarded: usize,
+        last_discard_reason: Option<DiscardReason>,
+        last_recycle_error: Option<RecycleError<E>>,
+    ) -> Self {
+        Self {
+            inner: Some(Box::new(ErrorContextInner {
+                elapsed,
+                status,
+                discarded,
+                last_discard_reason,
+                last_recycle_error,
+                attempts: 1,
+            })),
+        }
+    }
+
+    /// Sets the number of attempts made by [`Pool::run_with_retry()`].
+    ///
+    /// [`Pool::run_with_retry()`]: super::Pool::run_with_retry
+    pub(crate) fn set_attempts(&mut self, attempts: usize) {
+        if let Some(inner) = &mut self.inner {
+            inner.attempts = attempts;
+        }
+    }
+
+    /// Checks whether this context is empty. This is the case for errors
+    /// which were not returned by a [`Pool`].
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.inner.is_none()
+    }
+
+    /// Returns the time spent trying to retrieve an object.
+    #[must_use]
+    pub fn elapsed(&self) -> Option<Duration> {
+        self.inner.as_ref().map(|inner| inner.elapsed)
+    }
+
+    /// Returns the [`Status`] of the [`Pool`] at the time of the failure.
+    ///
+    /// [`Pool`]: super::Pool
+    #[must_use]
+    pub fn status(&self) -> Option<Status> {
+        self.inner.as_ref().map(|inner| inner.status)
+    }
+
+    /// Returns the number of idle objects which were discarded while
+    /// trying to retrieve an object, e.g. because they failed to be
+    /// recycled.
+    #[must_use]
+    pub fn discarded(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.discarded)
+    }
+
+    /// Returns the [`DiscardReason`] of the last object which was discarded
+    /// while trying to retrieve an object.
+    #[must_use]
+    pub fn last_discard_reason(&self) -> Option<DiscardReason> {
+        self.inner
+            .as_ref()
+            .and_then(|inner| inner.last_discard_reason)
+    }
+
+    /// Returns the number of attempts made, e.g. by
+    /// [`Pool::run_with_retry()`]. This is `0` if the context is empty.
+    ///
+    /// [`Pool::run_with_retry()`]: super::Pool::run_with_retry
+    #[must_use]
+    pub fn attempts(&self) -> usize {
+        self.inner.as_ref().map_or(0, |inner| inner.attempts)
+    }
+
+    /// Returns the [`RecycleError`] of the last object which failed to be
+    /// recycled while trying to retrieve an object.
+    #[must_use]
+    pub fn last_recycle_error(&self) -> Option<&RecycleError<E>> {
+        self.inner
+            .as_ref()
+            .and_then(|inner| inner.last_recycle_error.as_ref())
+    }
+}
+
+// Implemented manually to avoid unnecessary trait bound on `E` type parameter.
+impl<E> Default for ErrorContext<E> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<E: fmt::Debug> fmt::Debug for ErrorContext<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.inner {
+            Some(inner) => inner.fmt(f),
+            None => f.write_str("ErrorContext(empty)"),
+        }
+    }
+}
+
+impl<E: fmt::Display> fmt::Display for ErrorContext<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let Some(inner) = &self.inner else {
+            return Ok(());
+        };
+        write!(
+            f,
+            "elapsed: {:?}, size: {}/{}, available: {}, waiting: {}",
+            inner.elapsed,
+            inner.status.size,
+            inner.status.max_size,
+            inner.status.available,
+            inner.status.waiting
+        )?;
+        if inner.attempts > 1 {
+            write!(f, ", attempts: {}", inner.attempts)?;
+        }
+        if inner.discarded > 0 {
+            write!(f, ", discarded: {}", inner.discarded)?;
+        }
+        if let Some(reason) = inner.last_discard_reason {
+            write!(f, ", last discard reason: {:?}", reason)?;
+        }
+        if let Some(e) = &inner.last_recycle_error {
+            write!(f, ", last recycle error: {}", e)?;
         }
+        Ok(())
     }
 }
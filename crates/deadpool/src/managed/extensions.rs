@@ -0,0 +1,119 @@
+use std::{
+    any::{Any, TypeId},
+    collections::HashMap,
+    fmt,
+};
+
+/// Type map for attaching user defined state to an [`Object`].
+///
+/// Every object owns its own [`Extensions`] which are kept for the whole
+/// life of the object, i.e. they survive recycling and are available on
+/// every checkout. They can be accessed via [`Object::extensions()`] and
+/// [`Object::extensions_mut()`] as well as from hooks and from
+/// [`Manager::recycle_with_extensions()`].
+///
+/// At most one value of every type can be stored.
+///
+/// [`Manager::recycle_with_extensions()`]: super::Manager::recycle_with_extensions
+/// [`Object`]: super::Object
+/// [`Object::extensions()`]: super::Object::extensions
+/// [`Object::extensions_mut()`]: super::Object::extensions_mut
+#[derive(Default)]
+pub struct Extensions {
+    map: HashMap<TypeId, Box<dyn Any + Send + Sync>>,
+}
+
+impl Extensions {
+    /// Creates an empty [`Extensions`] map.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Inserts a value into this map.
+    ///
+    /// If a value of this type already existed it is replaced and returned.
+    pub fn insert<T: Send + Sync + 'static>(&mut self, value: T) -> Option<T> {
+        self.map
+            .insert(TypeId::of::<T>(), Box::new(value))
+            .and_then(|prev| prev.downcast().ok().map(|prev| *prev))
+    }
+
+    /// Returns a reference to the value of the given type.
+    #[must_use]
+    pub fn get<T: Send + Sync + 'static>(&self) -> Option<&T> {
+        self.map
+            .get(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_ref())
+    }
+
+    /// Returns a mutable reference to the value of the given type.
+    pub fn get_mut<T: Send + Sync + 'static>(&mut self) -> Option<&mut T> {
+        self.map
+            .get_mut(&TypeId::of::<T>())
+            .and_then(|value| value.downcast_mut())
+    }
+
+    /// Returns a mutable reference to the value of the given type and
+    /// inserts the value returned by `f` if none exists, yet.
+    pub fn get_or_insert_with<T: Send + Sync + 'static>(
+        &mut self,
+        f: impl FnOnce() -> T,
+    ) -> &mut T {
+        self.map
+            .entry(TypeId::of::<T>())
+            .or_insert_with(|| Box::new(f()))
+            .downcast_mut()
+            .unwrap()
+    }
+
+    /// Removes the value of the given type from this map and returns it.
+    pub fn remove<T: Send + Sync + 'static>(&mut self) -> Option<T> {
+        self.map
+            .remove(&TypeId::of::<T>())
+            .and_then(|value| value.downcast().ok().map(|value| *value))
+    }
+
+    /// Checks whether this map contains a value of the given type.
+    #[must_use]
+    pub fn contains<T: Send + Sync + 'static>(&self) -> bool {
+        self.map.contains_key(&TypeId::of::<T>())
+    }
+
+    /// Removes all values from this map.
+    pub fn clear(&mut self) {
+        self.map.clear();
+    }
+
+    /// Returns the number of values stored in this map.
+    #[must_use]
+    pub fn len(&self) -> usize {
+        self.map.len()
+    }
+
+    /// Checks whether this map is empty.
+    #[must_use]
+    pub fn is_empty(&self) -> bool {
+        self.map.is_empty()
+    }
+}
+
+// Implemented manually as the stored values don't need to implement `Debug`.
+impl fmt::Debug for Extensions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Extensions")
+            .field("len", &self.map.len())
+            .finish_non_exhaustive()
+    }
+}
+
+/// Key of the last checkout via [`Pool::get_with_affinity()`] or
+/// [`Pool::get_with_affinity_strict()`].
+///
+/// It is stored in the [`Extensions`] of the object and can be used to
+/// find out which key an object was last used with.
+///
+/// [`Pool::get_with_affinity()`]: super::Pool::get_with_affinity
+/// [`Pool::get_with_affinity_strict()`]: super::Pool::get_with_affinity_strict
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub struct Affinity<K>(pub K);
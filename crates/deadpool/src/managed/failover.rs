@@ -0,0 +1,310 @@
+//! [`Manager`] failing over between multiple backends.
+
+use std::{
+    fmt,
+    ops::{Deref, DerefMut},
+    sync::{
+        Mutex,
+        atomic::{AtomicBool, Ordering},
+    },
+    time::Duration,
+};
+
+use deadpool_runtime::{Runtime, timeout};
+
+use super::{Extensions, Manager, Metrics, RecycleError, RecycleResult, metrics::Instant};
+
+/// [`Manager`] wrapping an ordered list of [`Manager`]s of which the first
+/// one is the primary and all others are secondaries.
+///
+/// [`Manager::create()`] tries the [`Manager`]s in order and returns the
+/// first object created successfully. A [`Manager`] which failed to create
+/// an object or timed out is skipped for the
+/// [`retry_interval`](Self::retry_interval) unless all of them failed.
+///
+/// Every object remembers the [`Manager`] which created it so recycling and
+/// detaching is always done by that [`Manager`]. Objects created by a
+/// secondary are retired when being recycled once a [`Manager`] preferred
+/// over it is healthy again.
+///
+/// Recycling never creates objects itself. If a preferred [`Manager`]
+/// failed and the [`retry_interval`](Self::retry_interval) has passed a
+/// single object of a secondary is retired instead so the [`Pool`] creates
+/// a new one which probes the preferred [`Manager`]. As this probe delays
+/// the checkout it is only done if a
+/// [`create_timeout`](Self::create_timeout) is set. Otherwise a failed
+/// [`Manager`] is only tried again when the [`Pool`] creates an object
+/// anyways.
+///
+/// [`Pool`]: super::Pool
+pub struct FailoverManager<M> {
+    backends: Vec<Backend<M>>,
+    create_timeout: Option<(Runtime, Duration)>,
+    retry_interval: Duration,
+}
+
+struct Backend<M> {
+    manager: M,
+    failed_at: Mutex<Option<Instant>>,
+    /// Set if an object was retired in order to probe this backend when
+    /// creating the next object.
+    probe: AtomicBool,
+}
+
+impl<M: Manager> FailoverManager<M> {
+    /// Creates a new [`FailoverManager`] using the given `primary` and
+    /// `secondaries` in the given order.
+    #[must_use]
+    pub fn new(primary: M, secondaries: impl IntoIterator<Item = M>) -> Self {
+        Self {
+            backends: std::iter::once(primary)
+                .chain(secondaries)
+                .map(|manager| Backend {
+                    manager,
+                    failed_at: Mutex::new(None),
+                    probe: AtomicBool::new(false),
+                })
+                .collect(),
+            create_timeout: None,
+            retry_interval: Duration::from_secs(10),
+        }
+    }
+
+    /// Sets a timeout for every call of [`Manager::create()`] of the wrapped
+    /// [`Manager`]s. If it is exceeded the next [`Manager`] is tried.
+    ///
+    /// The given [`Runtime`] is used for the timeout. Failed [`Manager`]s
+    /// are only probed while recycling if this timeout is set.
+    #[must_use]
+    pub fn create_timeout(mut self, runtime: Runtime, value: Duration) -> Self {
+        self.create_timeout = Some((runtime, value));
+        self
+    }
+
+    /// Sets the duration for which a [`Manager`] is skipped after failing
+    /// to create an object.
+    ///
+    /// Default: 10 seconds
+    #[must_use]
+    pub fn retry_interval(mut self, value: Duration) -> Self {
+        self.retry_interval = value;
+        self
+    }
+
+    /// Returns the [`Manager`] at the given `index`, `0` being the primary.
+    #[must_use]
+    pub fn manager(&self, index: usize) -> Option<&M> {
+        self.backends.get(index).map(|backend| &backend.manager)
+    }
+
+    /// Checks whether the [`Manager`] at the given `index` created its last
+    /// object successfully.
+    #[must_use]
+    pub fn is_healthy(&self, index: usize) -> bool {
+        self.backends
+            .get(index)
+            .is_some_and(|backend| backend.failed_at.lock().unwrap().is_none())
+    }
+
+    async fn try_create(&self, index: usize) -> Result<M::Type, FailoverError<M::Error>> {
+        let backend = &self.backends[index];
+        let result = match self.create_timeout {
+            Some((runtime, duration)) => timeout(runtime, duration, backend.manager.create())
+                .await
+                .map_or(Err(FailoverError::Timeout), |r| {
+                    r.map_err(FailoverError::Backend)
+                }),
+            None => backend
+                .manager
+                .create()
+                .await
+                .map_err(FailoverError::Backend),
+        };
+        *backend.failed_at.lock().unwrap() = match result {
+            Ok(_) => None,
+            Err(_) => Some(Instant::now()),
+        };
+        backend.probe.store(false, Ordering::Relaxed);
+        result
+    }
+
+    /// Checks whether an object created by the [`Manager`] at the given
+    /// `index` should be retired in favor of a preferred [`Manager`].
+    fn should_retire(&self, index: usize) -> bool {
+        let probe = self.create_timeout.is_some();
+        for backend in &self.backends[..index] {
+            let mut failed_at = backend.failed_at.lock().unwrap();
+            match *failed_at {
+                None => return true,
+                // Claim the probe so concurrent recycles don't retire
+                // their objects, too.
+                Some(t) if probe && t.elapsed() >= self.retry_interval => {
+                    *failed_at = Some(Instant::now());
+                    backend.probe.store(true, Ordering::Relaxed);
+                    return true;
+                }
+                Some(_) => {}
+            }
+        }
+        false
+    }
+}
+
+impl<M: Manager> Manager for FailoverManager<M> {
+    type Type = FailoverObject<M::Type>;
+    type Error = FailoverError<M::Error>;
+
+    async fn create(&self) -> Result<Self::Type, Self::Error> {
+        let retry_interval = self.retry_interval;
+        let mut candidates = (0..self.backends.len())
+            .filter(|&index| {
+                let backend = &self.backends[index];
+                backend.probe.load(Ordering::Relaxed)
+                    || backend
+                        .failed_at
+                        .lock()
+                        .unwrap()
+                        .is_none_or(|t| t.elapsed() >= retry_interval)
+            })
+            .collect::<Vec<_>>();
+        if candidates.is_empty() {
+            candidates.extend(0..self.backends.len());
+        }
+        let mut last_error = FailoverError::Timeout;
+        for index in candidates {
+            match self.try_create(index).await {
+                Ok(obj) => return Ok(FailoverObject { obj, index }),
+                Err(e) => last_error = e,
+            }
+        }
+        Err(last_error)
+    }
+
+    async fn recycle(&self, obj: &mut Self::Type, metrics: &Metrics) -> RecycleResult<Self::Error> {
+        if self.should_retire(obj.index) {
+            return Err(RecycleError::message(
+                "preferred manager is available again",
+            ));
+        }
+        self.backends[obj.index]
+            .manager
+            .recycle(&mut obj.obj, metrics)
+            .await
+            .map_err(map_recycle_error)
+    }
+
+    async fn recycle_with_extensions(
+        &self,
+        obj: &mut Self::Type,
+        metrics: &Metrics,
+        extensions: &mut Extensions,
+    ) -> RecycleResult<Self::Error> {
+        if self.should_retire(obj.index) {
+            return Err(RecycleError::message(
+                "preferred manager is available again",
+            ));
+        }
+        self.backends[obj.index]
+            .manager
+            .recycle_with_extensions(&mut obj.obj, metrics, extensions)
+            .await
+            .map_err(map_recycle_error)
+    }
+
+    fn detach(&self, obj: &mut Self::Type) {
+        self.backends[obj.index].manager.detach(&mut obj.obj);
+    }
+
+    fn is_connection_error(&self, error: &Self::Error) -> bool {
+        match error {
+            FailoverError::Backend(e) => self.backends[0].manager.is_connection_error(e),
+            FailoverError::Timeout => true,
+        }
+    }
+}
+
+// Implemented manually as the wrapped managers don't need to implement
+// `Debug`.
+impl<M> fmt::Debug for FailoverManager<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FailoverManager")
+            .field("managers", &self.backends.len())
+            .field("create_timeout", &self.create_timeout)
+            .field("retry_interval", &self.retry_interval)
+            .finish_non_exhaustive()
+    }
+}
+
+fn map_recycle_error<E>(e: RecycleError<E>) -> RecycleError<FailoverError<E>> {
+    match e {
+        RecycleError::Message(msg) => RecycleError::Message(msg),
+        RecycleError::Backend(e) => RecycleError::Backend(FailoverError::Backend(e)),
+    }
+}
+
+/// Object created by a [`FailoverManager`].
+///
+/// It derefs to the object created by the wrapped [`Manager`].
+#[derive(Debug)]
+pub struct FailoverObject<T> {
+    obj: T,
+    index: usize,
+}
+
+impl<T> FailoverObject<T> {
+    /// Returns the index of the [`Manager`] which created this object, `0`
+    /// being the primary.
+    #[must_use]
+    pub fn index(&self) -> usize {
+        self.index
+    }
+
+    /// Returns the object created by the wrapped [`Manager`].
+    pub fn into_inner(self) -> T {
+        self.obj
+    }
+}
+
+impl<T> Deref for FailoverObject<T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        &self.obj
+    }
+}
+
+impl<T> DerefMut for FailoverObject<T> {
+    fn deref_mut(&mut self) -> &mut T {
+        &mut self.obj
+    }
+}
+
+/// Possible errors returned by a [`FailoverManager`].
+#[derive(Debug)]
+pub enum FailoverError<E> {
+    /// Error returned by a wrapped [`Manager`]. When creating an object
+    /// this is the error of the last [`Manager`] tried.
+    Backend(E),
+
+    /// Creating an object exceeded the
+    /// [`create_timeout`](FailoverManager::create_timeout).
+    Timeout,
+}
+
+impl<E: fmt::Display> fmt::Display for FailoverError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Backend(e) => write!(f, "{}", e),
+            Self::Timeout => write!(f, "Timeout occurred while creating an object"),
+        }
+    }
+}
+
+impl<E: std::error::Error + 'static> std::error::Error for FailoverError<E> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Backend(e) => Some(e),
+            Self::Timeout => None,
+        }
+    }
+}
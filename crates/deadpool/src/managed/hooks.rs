@@ -1,10 +1,11 @@
-//! Hooks allowing to run code when creating and/or recycling objects.
+//! Hooks allowing to run code when creating, recycling and/or discarding
+//! objects.
 
 use std::{borrow::Cow, fmt, future::Future, pin::Pin};
 
 use crate::managed::object::ObjectInner;
 
-use super::{Manager, Metrics};
+use super::{Extensions, Manager, Metrics, ObjectId};
 
 /// The result returned by hooks
 pub type HookResult<E> = Result<(), HookError<E>>;
@@ -21,12 +22,36 @@ type AsyncFn<M> = dyn for<'a> Fn(&'a mut <M as Manager>::Type, &'a Metrics) -> H
     + Sync
     + Send;
 
+/// Function signature for sync callbacks with access to the [`Extensions`]
+type SyncExtFn<M> = dyn Fn(&mut <M as Manager>::Type, &Metrics, &mut Extensions) -> HookResult<<M as Manager>::Error>
+    + Sync
+    + Send;
+
+/// Function signature for async callbacks with access to the [`Extensions`]
+type AsyncExtFn<M> = dyn for<'a> Fn(
+        &'a mut <M as Manager>::Type,
+        &'a Metrics,
+        &'a mut Extensions,
+    ) -> HookFuture<'a, <M as Manager>::Error>
+    + Sync
+    + Send;
+
+/// Function signature for `on_discard` callbacks
+type DiscardFn = dyn Fn(ObjectId, &Metrics, DiscardReason) + Sync + Send;
+
 /// Wrapper for hook functions
+#[non_exhaustive]
 pub enum Hook<M: Manager> {
     /// Use a plain function (non-async) as a hook
     Fn(Box<SyncFn<M>>),
     /// Use an async function as a hook
     AsyncFn(Box<AsyncFn<M>>),
+    /// Use a plain function (non-async) with access to the [`Extensions`]
+    /// of the object as a hook
+    FnWithExtensions(Box<SyncExtFn<M>>),
+    /// Use an async function with access to the [`Extensions`] of the
+    /// object as a hook
+    AsyncFnWithExtensions(Box<AsyncExtFn<M>>),
 }
 
 impl<M: Manager> Hook<M> {
@@ -45,6 +70,24 @@ impl<M: Manager> Hook<M> {
     ) -> Self {
         Self::AsyncFn(Box::new(f))
     }
+    /// Create Hook from sync function with access to the [`Extensions`]
+    pub fn sync_fn_with_extensions(
+        f: impl Fn(&mut M::Type, &Metrics, &mut Extensions) -> HookResult<M::Error>
+        + Sync
+        + Send
+        + 'static,
+    ) -> Self {
+        Self::FnWithExtensions(Box::new(f))
+    }
+    /// Create Hook from async function with access to the [`Extensions`]
+    pub fn async_fn_with_extensions(
+        f: impl for<'a> Fn(&'a mut M::Type, &'a Metrics, &'a mut Extensions) -> HookFuture<'a, M::Error>
+        + Sync
+        + Send
+        + 'static,
+    ) -> Self {
+        Self::AsyncFnWithExtensions(Box::new(f))
+    }
 }
 
 impl<M: Manager> fmt::Debug for Hook<M> {
@@ -58,12 +101,14 @@ impl<M: Manager> fmt::Debug for Hook<M> {
                 .debug_tuple("AsyncFn")
                 //.field(arg0)
                 .finish(),
+            Self::FnWithExtensions(_) => f.debug_tuple("FnWithExtensions").finish(),
+            Self::AsyncFnWithExtensions(_) => f.debug_tuple("AsyncFnWithExtensions").finish(),
         }
     }
 }
 
-/// Error which is returned by `post_create`, `pre_recycle` and
-/// `post_recycle` hooks.
+/// Error which is returned by `post_create`, `pre_recycle`, `post_recycle`
+/// and `pre_return` hooks.
 #[derive(Debug)]
 pub enum HookError<E> {
     /// Hook failed for some other reason.
@@ -99,6 +144,95 @@ impl<E: std::error::Error + 'static> std::error::Error for HookError<E> {
     }
 }
 
+/// Reason for discarding an object which is passed to `on_discard`
+/// callbacks.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[non_exhaustive]
+pub enum DiscardReason {
+    /// [`Manager::recycle()`] returned an error.
+    RecycleFailed,
+
+    /// [`Manager::recycle()`] timed out.
+    RecycleTimeout,
+
+    /// A `pre_recycle` hook returned an error.
+    PreRecycleHookFailed,
+
+    /// A `post_recycle` hook returned an error.
+    PostRecycleHookFailed,
+
+    /// A `pre_return` hook returned an error while releasing the object
+    /// via [`Object::release()`].
+    ///
+    /// [`Object::release()`]: super::Object::release
+    PreReturnHookFailed,
+
+    /// The object was invalidated via [`Pool::invalidate()`],
+    /// [`Pool::invalidate_where()`] or [`Pool::replace_manager()`].
+    ///
+    /// [`Pool::invalidate()`]: super::Pool::invalidate
+    /// [`Pool::invalidate_where()`]: super::Pool::invalidate_where
+    /// [`Pool::replace_manager()`]: super::Pool::replace_manager
+    Invalidated,
+
+    /// The object has been checked out [`PoolConfig::max_uses`] times.
+    ///
+    /// [`PoolConfig::max_uses`]: super::PoolConfig::max_uses
+    MaxUsesReached,
+
+    /// The [`Pool`] was shrunk via [`Pool::resize()`] and had no room for
+    /// the object.
+    ///
+    /// [`Pool`]: super::Pool
+    /// [`Pool::resize()`]: super::Pool::resize
+    Resized,
+
+    /// The object was discarded in order to make room for a new object
+    /// when no idle object matched [`Pool::get_where()`] or
+    /// [`Pool::get_with_affinity_strict()`].
+    ///
+    /// [`Pool::get_where()`]: super::Pool::get_where
+    /// [`Pool::get_with_affinity_strict()`]: super::Pool::get_with_affinity_strict
+    Evicted,
+
+    /// An operation passed to [`Pool::run()`] failed with an error which
+    /// the [`Manager`] classified as a connection error.
+    ///
+    /// [`Manager`]: super::Manager
+    /// [`Pool::run()`]: super::Pool::run
+    Broken,
+
+    /// The object was idle and discarded in favor of another [`Pool`]
+    /// sharing the same [`Budget`].
+    ///
+    /// [`Budget`]: super::Budget
+    /// [`Pool`]: super::Pool
+    Reclaimed,
+}
+
+#[derive(Default)]
+pub(crate) struct DiscardHooks {
+    vec: Vec<Box<DiscardFn>>,
+}
+
+// Implemented manually as the callbacks don't implement `Debug`.
+impl fmt::Debug for DiscardHooks {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DiscardHooks").finish_non_exhaustive()
+    }
+}
+
+impl DiscardHooks {
+    pub(crate) fn apply(&self, id: ObjectId, metrics: &Metrics, reason: DiscardReason) {
+        for f in &self.vec {
+            f(id, metrics, reason);
+        }
+    }
+    pub(crate) fn push(&mut self, f: Box<DiscardFn>) {
+        self.vec.push(f);
+    }
+}
+
 pub(crate) struct HookVec<M: Manager> {
     vec: Vec<Hook<M>>,
 }
@@ -128,6 +262,12 @@ impl<M: Manager> HookVec<M> {
             match hook {
                 Hook::Fn(f) => f(&mut inner.obj, &inner.metrics)?,
                 Hook::AsyncFn(f) => f(&mut inner.obj, &inner.metrics).await?,
+                Hook::FnWithExtensions(f) => {
+                    f(&mut inner.obj, &inner.metrics, &mut inner.extensions)?
+                }
+                Hook::AsyncFnWithExtensions(f) => {
+                    f(&mut inner.obj, &inner.metrics, &mut inner.extensions).await?
+                }
             };
         }
         Ok(())
@@ -144,6 +284,8 @@ pub(crate) struct Hooks<M: Manager> {
     pub(crate) post_create: HookVec<M>,
     pub(crate) pre_recycle: HookVec<M>,
     pub(crate) post_recycle: HookVec<M>,
+    pub(crate) pre_return: HookVec<M>,
+    pub(crate) on_discard: DiscardHooks,
 }
 
 // Implemented manually to avoid unnecessary trait bound on `M` type parameter.
@@ -153,6 +295,8 @@ impl<M: Manager> fmt::Debug for Hooks<M> {
             .field("post_create", &self.post_create)
             .field("pre_recycle", &self.pre_recycle)
             .field("post_recycle", &self.post_recycle)
+            .field("pre_return", &self.pre_return)
+            .field("on_discard", &self.on_discard)
             .finish()
     }
 }
@@ -164,6 +308,8 @@ impl<M: Manager> Default for Hooks<M> {
             pre_recycle: HookVec::default(),
             post_create: HookVec::default(),
             post_recycle: HookVec::default(),
+            pre_return: HookVec::default(),
+            on_discard: DiscardHooks::default(),
         }
     }
 }
@@ -1,6 +1,6 @@
 use std::future::Future;
 
-use crate::managed::{Metrics, RecycleError};
+use crate::managed::{Extensions, Metrics, RecycleError};
 
 /// Manager responsible for creating new [`super::Object`]s or recycling existing ones.
 pub trait Manager: Sync + Send {
@@ -24,6 +24,25 @@ pub trait Manager: Sync + Send {
         metrics: &Metrics,
     ) -> impl Future<Output = RecycleResult<Self::Error>> + Send;
 
+    /// Tries to recycle an instance of [`Manager::Type`] with access to the
+    /// [`Extensions`] of the [`super::Object`].
+    ///
+    /// This is the method actually called by the [`super::Pool`]. The default
+    /// implementation ignores the `extensions` and calls
+    /// [`Manager::recycle()`].
+    ///
+    /// # Errors
+    ///
+    /// Returns [`RecycleError`]<[`Manager::Error`]> if the instance couldn't be recycled.
+    fn recycle_with_extensions(
+        &self,
+        obj: &mut Self::Type,
+        metrics: &Metrics,
+        _extensions: &mut Extensions,
+    ) -> impl Future<Output = RecycleResult<Self::Error>> + Send {
+        self.recycle(obj, metrics)
+    }
+
     /// Detaches an instance of [`Manager::Type`] from this [`Manager`].
     ///
     /// This method is called when using the [`super::Object::take()`] method for
@@ -31,6 +50,16 @@ pub trait Manager: Sync + Send {
     /// any references to the handed out [`super::Object`]s then the default
     /// implementation can be used which does nothing.
     fn detach(&self, _obj: &mut Self::Type) {}
+
+    /// Checks whether the given error means that the connection of an
+    /// instance of [`Manager::Type`] is broken.
+    ///
+    /// This is used by [`super::Pool::run()`] for discarding broken
+    /// [`super::Object`]s and retrying the operation. The default
+    /// implementation returns `false` which disables retries.
+    fn is_connection_error(&self, _error: &Self::Error) -> bool {
+        false
+    }
 }
 
 /// Result type of the [`Manager::recycle()`] method.
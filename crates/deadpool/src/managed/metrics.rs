@@ -1,14 +1,18 @@
+use std::time::Duration;
+
+// `std::time::Instant` panics on `wasm32` targets which is why `web_time`
+// is used instead. On all other targets it is the very same type.
 #[cfg(not(target_arch = "wasm32"))]
-use std::time::{Duration, Instant};
+pub(crate) use std::time::Instant;
+#[cfg(target_arch = "wasm32")]
+pub(crate) use web_time::Instant;
 
 /// Statistics regarding an object returned by the pool
 #[derive(Clone, Copy, Debug)]
 #[must_use]
 pub struct Metrics {
-    #[cfg(not(target_arch = "wasm32"))]
     /// The instant when this object was created
     pub created: Instant,
-    #[cfg(not(target_arch = "wasm32"))]
     /// The instant when this object was last used
     pub recycled: Option<Instant>,
     /// The number of times the objects was recycled
@@ -16,12 +20,10 @@ pub struct Metrics {
 }
 
 impl Metrics {
-    #[cfg(not(target_arch = "wasm32"))]
     /// Access the age of this object
     pub fn age(&self) -> Duration {
         self.created.elapsed()
     }
-    #[cfg(not(target_arch = "wasm32"))]
     /// Get the time elapsed when this object was last used
     pub fn last_used(&self) -> Duration {
         self.recycled.unwrap_or(self.created).elapsed()
@@ -31,9 +33,7 @@ impl Metrics {
 impl Default for Metrics {
     fn default() -> Self {
         Self {
-            #[cfg(not(target_arch = "wasm32"))]
             created: Instant::now(),
-            #[cfg(not(target_arch = "wasm32"))]
             recycled: None,
             recycle_count: 0,
         }
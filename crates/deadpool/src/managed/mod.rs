@@ -48,26 +48,38 @@
 //! For a more complete example please see
 //! [`deadpool-postgres`](https://crates.io/crates/deadpool-postgres) crate.
 
+mod balanced;
+mod budget;
 mod builder;
 mod config;
 mod dropguard;
 mod errors;
+mod extensions;
+mod failover;
 mod hooks;
 mod manager;
 mod metrics;
 mod object;
 mod pool;
 pub mod reexports;
+mod shared;
+mod tracking;
 
 pub use crate::Status;
 
 pub use self::{
-    builder::{BuildError, PoolBuilder},
-    config::{CreatePoolError, PoolConfig, QueueMode, Timeouts},
-    errors::{PoolError, RecycleError, TimeoutType},
-    hooks::{Hook, HookError, HookFuture, HookResult},
+    balanced::{BalanceStrategy, BalancedManager, BalancedObject},
+    budget::Budget,
+    builder::{BuildError, PoolBuilder, WarmUpError},
+    config::{CreatePoolError, PoolConfig, QueueMode, RetryPolicy, Timeouts},
+    errors::{ErrorContext, PoolError, RecycleError, TimeoutType},
+    extensions::{Affinity, Extensions},
+    failover::{FailoverError, FailoverManager, FailoverObject},
+    hooks::{DiscardReason, Hook, HookError, HookFuture, HookResult},
     manager::{Manager, RecycleResult},
     metrics::Metrics,
-    object::{Object, ObjectId},
-    pool::{Pool, RetainResult, WeakPool},
+    object::{MappedObject, Object, ObjectId},
+    pool::{Pool, Reservation, RetainResult, RetirePolicy, WeakPool},
+    shared::{SharedObject, SharedPool},
+    tracking::{TrackCheckouts, track_checkouts},
 };
@@ -1,9 +1,14 @@
 use std::{
     fmt,
     ops::{Deref, DerefMut},
+    ptr,
+    sync::Arc,
 };
 
-use crate::managed::{Manager, Metrics, Pool, WeakPool};
+use crate::managed::{
+    DiscardReason, Extensions, HookError, Manager, Metrics, Pool, RecycleError, WeakPool,
+    budget::BudgetPermit, metrics::Instant, pool::Generation, tracking::CheckoutRecord,
+};
 
 /// Wrapper around the actual pooled object which implements [`Deref`],
 /// [`DerefMut`] and [`Drop`] traits.
@@ -17,6 +22,11 @@ pub struct Object<M: Manager> {
 
     /// Pool to return the pooled object to.
     pub(crate) pool: WeakPool<M>,
+
+    /// Record of this checkout if it happened within [`track_checkouts()`].
+    ///
+    /// [`track_checkouts()`]: super::track_checkouts
+    pub(crate) _checkout: Option<CheckoutRecord>,
 }
 
 impl<M> fmt::Debug for Object<M>
@@ -43,20 +53,55 @@ pub(crate) struct ObjectInner<M: Manager> {
     /// or simply identify an objects for debugging purposes.
     pub id: usize,
 
+    /// Generation of the [`Pool`] at the time this object was created.
+    ///
+    /// Objects from an older generation might have been invalidated via
+    /// [`Pool::invalidate()`] or [`Pool::invalidate_where()`].
+    pub generation: Generation,
+
+    /// The [`Manager`] which created this object.
+    pub manager: Arc<M>,
+
     /// Object metrics.
     pub metrics: Metrics,
+
+    /// User defined state attached to this object.
+    pub extensions: Extensions,
+
+    /// The instant when this object was last known to work, i.e. when it
+    /// was created, recycled by the [`Manager`] or used successfully via
+    /// [`Pool::run()`].
+    pub validated: Instant,
+
+    /// Number of times this object was handed out by the [`Pool`]. This is
+    /// what [`PoolConfig::max_uses`] is checked against.
+    ///
+    /// [`PoolConfig::max_uses`]: super::PoolConfig::max_uses
+    pub checkouts: usize,
+
+    /// Permit of the [`Budget`] the [`Pool`] is attached to. It is
+    /// released when this object is dropped.
+    ///
+    /// [`Budget`]: super::Budget
+    pub _budget_permit: Option<BudgetPermit>,
 }
 
 impl<M: Manager> Object<M> {
     /// Takes this [`Object`] from its [`Pool`] permanently. This reduces the
     /// size of the [`Pool`].
+    ///
+    /// **Important:** If the [`Pool`] uses a [`Budget`] the taken object
+    /// no longer counts towards it. Taking an open connection therefore
+    /// allows more connections than the [`Budget`] permits.
+    ///
+    /// [`Budget`]: super::Budget
     #[must_use]
     pub fn take(mut this: Self) -> M::Type {
-        let mut inner = this.inner.take().unwrap().obj;
+        let mut inner = this.inner.take().unwrap();
         if let Some(pool) = Object::pool(&this) {
             pool.inner.detach_object(&mut inner)
         }
-        inner
+        inner.obj
     }
 
     /// Returns the unique ID of this object.
@@ -73,6 +118,20 @@ impl<M: Manager> Object<M> {
         &this.inner.as_ref().unwrap().metrics
     }
 
+    /// Returns the [`Extensions`] of this object.
+    ///
+    /// They are kept for the whole life of the object and survive recycling.
+    pub fn extensions(this: &Self) -> &Extensions {
+        &this.inner.as_ref().unwrap().extensions
+    }
+
+    /// Returns the [`Extensions`] of this object mutably.
+    ///
+    /// They are kept for the whole life of the object and survive recycling.
+    pub fn extensions_mut(this: &mut Self) -> &mut Extensions {
+        &mut this.inner.as_mut().unwrap().extensions
+    }
+
     /// Returns the [`Pool`] this [`Object`] belongs to.
     ///
     /// Since [`Object`]s only hold a [`std::sync::Weak`] reference to the
@@ -80,6 +139,69 @@ impl<M: Manager> Object<M> {
     pub fn pool(this: &Self) -> Option<Pool<M>> {
         this.pool.upgrade()
     }
+
+    /// Returns this object to its [`Pool`] after running the `pre_return`
+    /// hooks.
+    ///
+    /// Unlike dropping an [`Object`] this allows running async cleanup, e.g.
+    /// resetting session state, and learning about its failure. If the
+    /// returned future is dropped before completion the object is returned
+    /// to the [`Pool`] just like when being dropped.
+    ///
+    /// # Errors
+    ///
+    /// If a `pre_return` hook fails the object is discarded with
+    /// [`DiscardReason::PreReturnHookFailed`] and the error is returned.
+    pub async fn release(mut this: Self) -> Result<(), RecycleError<M::Error>> {
+        let Some(pool) = Object::pool(&this) else {
+            return Ok(());
+        };
+        let result = pool.inner.pre_return(this.inner.as_mut().unwrap()).await;
+        if let Err(e) = result {
+            let mut inner = this.inner.take().unwrap();
+            pool.inner
+                .report_discard(&inner, DiscardReason::PreReturnHookFailed);
+            pool.inner.detach_object(&mut inner);
+            return Err(match e {
+                HookError::Message(msg) => RecycleError::Message(msg),
+                HookError::Backend(e) => RecycleError::Backend(e),
+            });
+        }
+        Ok(())
+    }
+
+    /// Makes a [`MappedObject`] for a component of this object, similar to
+    /// [`std::cell::RefMut::map()`]. The whole object is still returned
+    /// to its [`Pool`] once the [`MappedObject`] is dropped.
+    ///
+    /// Unlike [`std::cell::RefMut::map()`] this takes two projections
+    /// instead of one, one for shared and one for exclusive access, as
+    /// this crate doesn't use any `unsafe` code. Both must return the same
+    /// component, which is checked once in debug builds. The projections
+    /// are stored in the [`MappedObject`] and must therefore be `'static`.
+    /// They may capture their environment, e.g. a key of a map.
+    ///
+    /// ```rust,ignore
+    /// let cache = Object::map(obj, |conn| &conn.cache, |conn| &mut conn.cache);
+    /// ```
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if the projections return different
+    /// components.
+    pub fn map<U, F, G>(mut this: Self, get: F, get_mut: G) -> MappedObject<M, U>
+    where
+        U: ?Sized,
+        F: Fn(&M::Type) -> &U + Send + Sync + 'static,
+        G: Fn(&mut M::Type) -> &mut U + Send + Sync + 'static,
+    {
+        debug_assert_same_component(&mut *this, &get, &get_mut);
+        MappedObject {
+            obj: this,
+            get: Box::new(get),
+            get_mut: Box::new(get_mut),
+        }
+    }
 }
 
 impl<M: Manager> Drop for Object<M> {
@@ -117,6 +239,133 @@ impl<M: Manager> AsMut<M::Type> for Object<M> {
     }
 }
 
+/// Component of an [`Object`] created via [`Object::map()`].
+///
+/// It derefs to the component and returns the whole [`Object`] to its
+/// [`Pool`] when being dropped. As it only requires an [`Object`] it can
+/// also be used for implementing the custom wrapper `W` of a
+/// [`Pool<M, W>`](Pool).
+#[must_use]
+pub struct MappedObject<M: Manager, U: ?Sized> {
+    obj: Object<M>,
+    get: Box<Project<M::Type, U>>,
+    get_mut: Box<ProjectMut<M::Type, U>>,
+}
+
+/// Checks that both projections passed to [`Object::map()`] or
+/// [`MappedObject::map()`] return the same component.
+fn debug_assert_same_component<T, U>(
+    t: &mut T,
+    get: impl Fn(&T) -> &U,
+    get_mut: impl Fn(&mut T) -> &mut U,
+) where
+    T: ?Sized,
+    U: ?Sized,
+{
+    if cfg!(debug_assertions) {
+        let shared: *const U = get(t);
+        let exclusive: *const U = get_mut(t);
+        assert!(
+            ptr::eq(shared, exclusive),
+            "the projections of a MappedObject must return the same component"
+        );
+    }
+}
+
+/// Projection of a [`MappedObject`] for shared access.
+type Project<T, U> = dyn Fn(&T) -> &U + Send + Sync;
+
+/// Projection of a [`MappedObject`] for exclusive access.
+type ProjectMut<T, U> = dyn Fn(&mut T) -> &mut U + Send + Sync;
+
+impl<M: Manager, U: ?Sized> MappedObject<M, U> {
+    /// Makes a [`MappedObject`] for a component of the component of this
+    /// [`MappedObject`].
+    ///
+    /// The given projections are applied to the component of this
+    /// [`MappedObject`] and combined with its own projections. As the
+    /// combined projections are stored as well, `M::Type` and `U` must be
+    /// `'static`. See [`Object::map()`] for details.
+    ///
+    /// # Panics
+    ///
+    /// Panics in debug builds if the projections return different
+    /// components.
+    pub fn map<V, F, G>(mut this: Self, get: F, get_mut: G) -> MappedObject<M, V>
+    where
+        M::Type: 'static,
+        U: 'static,
+        V: ?Sized,
+        F: Fn(&U) -> &V + Send + Sync + 'static,
+        G: Fn(&mut U) -> &mut V + Send + Sync + 'static,
+    {
+        debug_assert_same_component(&mut *this, &get, &get_mut);
+        let Self {
+            obj,
+            get: outer,
+            get_mut: outer_mut,
+        } = this;
+        MappedObject {
+            obj,
+            get: Box::new(move |t| get(outer(t))),
+            get_mut: Box::new(move |t| get_mut(outer_mut(t))),
+        }
+    }
+
+    /// Returns the [`Object`] this component belongs to.
+    pub fn object(this: &Self) -> &Object<M> {
+        &this.obj
+    }
+
+    /// Returns the [`Object`] this component belongs to mutably.
+    pub fn object_mut(this: &mut Self) -> &mut Object<M> {
+        &mut this.obj
+    }
+
+    /// Turns this [`MappedObject`] back into the [`Object`] it was created
+    /// from.
+    pub fn into_object(this: Self) -> Object<M> {
+        this.obj
+    }
+}
+
+impl<M: Manager, U: ?Sized> Deref for MappedObject<M, U> {
+    type Target = U;
+    fn deref(&self) -> &U {
+        (self.get)(&self.obj)
+    }
+}
+
+impl<M: Manager, U: ?Sized> DerefMut for MappedObject<M, U> {
+    fn deref_mut(&mut self) -> &mut U {
+        (self.get_mut)(&mut self.obj)
+    }
+}
+
+impl<M: Manager, U: ?Sized> AsRef<U> for MappedObject<M, U> {
+    fn as_ref(&self) -> &U {
+        self
+    }
+}
+
+impl<M: Manager, U: ?Sized> AsMut<U> for MappedObject<M, U> {
+    fn as_mut(&mut self) -> &mut U {
+        self
+    }
+}
+
+impl<M, U> fmt::Debug for MappedObject<M, U>
+where
+    M: Manager,
+    U: fmt::Debug + ?Sized,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("MappedObject")
+            .field("obj", &&**self)
+            .finish_non_exhaustive()
+    }
+}
+
 /// A unique identifier for an object within a pool.
 ///
 /// `ObjectId` is an opaque wrapper around a numeric identifier.
@@ -131,7 +380,7 @@ impl<M: Manager> AsMut<M::Type> for Object<M> {
 /// it can be compared, ordered, or stored in sets and maps.
 /// It should not be used for arithmetic or treated as a raw number.
 #[derive(Copy, Clone, Debug, Hash, Eq, PartialEq, Ord, PartialOrd)]
-pub struct ObjectId(usize);
+pub struct ObjectId(pub(crate) usize);
 
 impl fmt::Display for ObjectId {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
@@ -1,26 +1,34 @@
-#[cfg(not(target_arch = "wasm32"))]
-use std::time::Instant;
 use std::{
-    collections::VecDeque,
+    collections::{BTreeMap, VecDeque},
     fmt,
-    future::Future,
+    future::{Future, pending, poll_fn},
     marker::PhantomData,
+    pin::{Pin, pin},
     sync::{
         Arc, Mutex, Weak,
         atomic::{AtomicUsize, Ordering},
     },
+    task::Poll,
     time::Duration,
 };
 
 use deadpool_runtime::{Runtime, timeout};
-use tokio::sync::{Semaphore, TryAcquireError};
+use tokio::sync::{Notify, Semaphore, TryAcquireError, futures::Notified, watch};
 
 use crate::{
     Status,
     managed::{
-        Manager, Metrics, Object, PoolBuilder, PoolConfig, PoolError, QueueMode, TimeoutType,
-        Timeouts, dropguard::DropGuard, hooks::Hooks, object::ObjectInner,
+        Affinity, DiscardReason, ErrorContext, Extensions, HookError, Manager, Metrics, Object,
+        ObjectId, PoolBuilder, PoolConfig, PoolError, QueueMode, RecycleError, RetryPolicy,
+        TimeoutType, Timeouts,
+        budget::{Budget, BudgetPermit, Reclaim},
+        dropguard::DropGuard,
+        hooks::Hooks,
+        metrics::Instant,
+        object::ObjectInner,
+        tracking,
     },
+    util::{block_on, try_join_all},
 };
 
 /// Generic object and connection pool.
@@ -65,23 +73,40 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     }
 
     pub(crate) fn from_builder(builder: PoolBuilder<M, W>) -> Self {
-        Self {
+        let (budget, register) = builder.budget.unzip();
+        let pool = Self {
             inner: Arc::new(PoolInner {
-                manager: builder.manager,
+                manager: Mutex::new(Arc::new(builder.manager)),
                 next_id: AtomicUsize::new(0),
+                generation: AtomicUsize::new(0),
+                invalidations: Arc::new(Mutex::new(Invalidations::default())),
                 slots: Mutex::new(Slots {
                     vec: VecDeque::with_capacity(builder.config.max_size),
                     size: 0,
                     max_size: builder.config.max_size,
                 }),
                 users: AtomicUsize::new(0),
+                reserved: AtomicUsize::new(0),
                 semaphore: Semaphore::new(builder.config.max_size),
                 config: builder.config,
                 hooks: builder.hooks,
                 runtime: builder.runtime,
+                budget,
+                returned: Notify::new(),
+                status_tx: watch::channel(Status {
+                    max_size: builder.config.max_size,
+                    size: 0,
+                    available: 0,
+                    waiting: 0,
+                })
+                .0,
             }),
             _wrapper: PhantomData,
+        };
+        if let (Some(budget), Some(register)) = (&pool.inner.budget, register) {
+            budget.register(register(&pool.inner));
         }
+        pool
     }
 
     /// Retrieves an [`Object`] from this [`Pool`] or waits for one to
@@ -101,9 +126,396 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     ///
     /// See [`PoolError`] for details.
     pub async fn timeout_get(&self, timeouts: &Timeouts) -> Result<W, PoolError<M::Error>> {
+        Ok(self.checkout(timeouts, None, None).await?.into())
+    }
+
+    /// Reserves a slot of this [`Pool`] or waits for one to become
+    /// available.
+    ///
+    /// The returned [`Reservation`] can later be turned into an [`Object`]
+    /// via [`Reservation::get()`] without waiting again. This is useful for
+    /// admitting work only if an [`Object`] is guaranteed to be available
+    /// for it. Dropping the [`Reservation`] gives the slot back. Held
+    /// reservations are neither reported as waiting nor as available in
+    /// the [`Status`].
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn reserve(&self) -> Result<Reservation<M, W>, PoolError<M::Error>> {
+        let timeouts = self.timeouts();
+        let start = Instant::now();
+        apply_timeout(
+            self.inner.runtime,
+            TimeoutType::Total,
+            timeouts.total,
+            self.acquire_slot(&timeouts),
+        )
+        .await
+        .map_err(|e| self.with_context(e, start, Discards::default()))?;
+        // The caller holds a slot but is neither waiting nor using an object.
+        let _ = self.inner.reserved.fetch_add(1, Ordering::Relaxed);
+        let _ = self.inner.users.fetch_sub(1, Ordering::Relaxed);
+        self.inner.notify_status();
+        Ok(Reservation {
+            pool: self.clone(),
+            reserved: true,
+        })
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] by blocking the current
+    /// thread until one becomes available.
+    ///
+    /// This makes it possible to share a [`Pool`] between async and
+    /// synchronous code, e.g. CLI tools or worker threads. The returned
+    /// object can be dropped from any thread.
+    ///
+    /// **Important:** This must not be called from within an async context
+    /// as it blocks the executor. If the [`Manager`] or the configured
+    /// [`Timeouts`] rely on a [`Runtime`] the current thread must be inside
+    /// the context of that runtime, e.g. by using
+    /// `tokio::runtime::Handle::enter()` with a multi-threaded runtime.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub fn blocking_get(&self) -> Result<W, PoolError<M::Error>> {
+        self.blocking_timeout_get(&self.timeouts())
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] by blocking the current
+    /// thread using a different `timeout` than the configured one.
+    ///
+    /// See [`Pool::blocking_get()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub fn blocking_timeout_get(&self, timeouts: &Timeouts) -> Result<W, PoolError<M::Error>> {
+        block_on(self.timeout_get(timeouts))
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] failing with
+    /// [`TimeoutType::Total`] if it can't be retrieved before the given
+    /// `deadline`.
+    ///
+    /// This is useful for honoring request deadlines propagated from
+    /// upstream services. The configured [`Timeouts`] still apply, e.g. if
+    /// [`Timeouts::total`] expires before the `deadline`.
+    ///
+    /// **Important:** This requires a [`Runtime`] to be specified.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_until(&self, deadline: Instant) -> Result<W, PoolError<M::Error>> {
+        Ok(self
+            .checkout(&self.timeouts(), None, Some(deadline))
+            .await?
+            .into())
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] preferring an idle object
+    /// which was last retrieved using the same `key`.
+    ///
+    /// This is useful if objects carry state which is expensive to set up,
+    /// e.g. a connection which is already configured for a specific tenant.
+    /// If there is no such object any idle object is used instead. The `key`
+    /// is stored as [`Affinity`] in the [`Extensions`] of the returned
+    /// object.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_with_affinity<K>(&self, key: K) -> Result<W, PoolError<M::Error>>
+    where
+        K: PartialEq + Send + Sync + 'static,
+    {
+        self.checkout_with_affinity(key, true).await
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] which was last retrieved
+    /// using the same `key`.
+    ///
+    /// This works like [`Pool::get_with_affinity()`] but never falls back to
+    /// an idle object with a different or no [`Affinity`]. If there is no
+    /// matching object a new one is created. If the [`Pool`] is full the
+    /// least recently used idle object is discarded in order to make room
+    /// for it.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_with_affinity_strict<K>(&self, key: K) -> Result<W, PoolError<M::Error>>
+    where
+        K: PartialEq + Send + Sync + 'static,
+    {
+        self.checkout_with_affinity(key, false).await
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] only using idle objects
+    /// matching the given `predicate`.
+    ///
+    /// If there is no matching object a new one is created. If the [`Pool`]
+    /// is full the least recently used idle object is discarded in order to
+    /// make room for it. Objects created by this method are not checked
+    /// against the `predicate`.
+    ///
+    /// **Caution:** The `predicate` is called while the [`Pool`] is locked.
+    /// Therefore it should not block.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn get_where(
+        &self,
+        predicate: impl Fn(ObjectId, &Metrics, &Extensions) -> bool + Sync,
+    ) -> Result<W, PoolError<M::Error>> {
+        Ok(self
+            .checkout(&self.timeouts(), Some((&predicate, false)), None)
+            .await?
+            .into())
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] and passes it to `f`
+    /// retrying the operation according to the default [`RetryPolicy`].
+    ///
+    /// **Caution:** The default [`RetryPolicy`] retries `f` on connection
+    /// errors. Only use this for idempotent operations. A connection might
+    /// break after the server already applied the operation, e.g. committed
+    /// a transaction, in which case it would be applied twice. Use
+    /// [`Pool::run_with_retry()`] with [`RetryPolicy::never()`] otherwise.
+    ///
+    /// See [`Pool::run_with_retry()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    pub async fn run<T, F>(&self, f: F) -> Result<T, PoolError<M::Error>>
+    where
+        F: AsyncFnMut(&mut Object<M>) -> Result<T, M::Error>,
+    {
+        self.run_with_retry(&RetryPolicy::default(), f).await
+    }
+
+    /// Retrieves an [`Object`] from this [`Pool`] and passes it to `f`
+    /// retrying the operation according to the given [`RetryPolicy`].
+    ///
+    /// The [`Object`] is returned to the [`Pool`] as soon as the future
+    /// returned by `f` completes. If it fails with an error which the
+    /// [`Manager`] classifies as a connection error via
+    /// [`Manager::is_connection_error()`] the [`Object`] is discarded
+    /// instead and the operation is retried using another [`Object`]. The
+    /// same applies to connection errors when creating a new [`Object`].
+    ///
+    /// **Caution:** Retrying is only safe for idempotent operations. See
+    /// [`Pool::run()`] for details.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details. Errors returned by `f` are wrapped in
+    /// [`PoolError::Operation`]. If all attempts fail the error of the last
+    /// attempt is returned. The [`ErrorContext`] of the error covers all
+    /// attempts.
+    pub async fn run_with_retry<T, F>(
+        &self,
+        policy: &RetryPolicy,
+        mut f: F,
+    ) -> Result<T, PoolError<M::Error>>
+    where
+        F: AsyncFnMut(&mut Object<M>) -> Result<T, M::Error>,
+    {
+        let start = Instant::now();
+        // Attaches the context of all attempts to the error.
+        let fail = |mut e: PoolError<M::Error>, attempts: usize| {
+            let ctx = e.context_mut();
+            if ctx.is_empty() {
+                *ctx = ErrorContext::from_checkout(start.elapsed(), self.status(), 0, None, None);
+            }
+            ctx.set_attempts(attempts);
+            e
+        };
+        let mut attempt = 1;
+        loop {
+            let error = match self.checkout(&self.timeouts(), None, None).await {
+                Ok(mut obj) => match f(&mut obj).await {
+                    Ok(value) => {
+                        obj.inner.as_mut().unwrap().validated = Instant::now();
+                        return Ok(value);
+                    }
+                    Err(e) => {
+                        let inner = obj.inner.as_mut().unwrap();
+                        if !inner.manager.is_connection_error(&e) {
+                            inner.validated = Instant::now();
+                            return Err(fail(
+                                PoolError::Operation(e, ErrorContext::new()),
+                                attempt,
+                            ));
+                        }
+                        let mut inner = obj.inner.take().unwrap();
+                        self.inner.report_discard(&inner, DiscardReason::Broken);
+                        self.inner.detach_object(&mut inner);
+                        PoolError::Operation(e, ErrorContext::new())
+                    }
+                },
+                Err(PoolError::Backend(e, ctx)) if self.manager().is_connection_error(&e) => {
+                    PoolError::Backend(e, ctx)
+                }
+                Err(e) => return Err(fail(e, attempt)),
+            };
+            if attempt >= policy.max_attempts {
+                return Err(fail(error, attempt));
+            }
+            attempt += 1;
+            if let Some(delay) = policy.delay {
+                let runtime = self.inner.runtime.ok_or_else(|| {
+                    fail(PoolError::NoRuntimeSpecified(ErrorContext::new()), attempt)
+                })?;
+                let _ = timeout(runtime, delay, pending::<()>()).await;
+            }
+        }
+    }
+
+    /// Creates objects concurrently until this [`Pool`] holds `n` objects.
+    ///
+    /// Objects which exist already, no matter if idle or checked out, count
+    /// towards `n`. `n` is capped at the maximum size of the [`Pool`]. The
+    /// new objects are added to the [`Pool`] as idle objects. This can be
+    /// used for refilling the [`Pool`] e.g. after a maintenance window.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details. The first error returned while
+    /// creating the objects is returned and all other objects which are
+    /// still being created are dropped.
+    pub async fn warm_up(&self, n: usize) -> Result<(), PoolError<M::Error>> {
+        let status = self.status();
+        let missing = n.min(status.max_size).saturating_sub(status.size);
+        let timeouts = self.timeouts();
+        let start = Instant::now();
+        apply_timeout(
+            self.inner.runtime,
+            TimeoutType::Total,
+            timeouts.total,
+            try_join_all((0..missing).map(|_| self.create_idle(&timeouts))),
+        )
+        .await
+        .map(drop)
+        .map_err(|e| self.with_context(e, start, Discards::default()))
+    }
+
+    /// Creates a new object and adds it to the idle objects without handing
+    /// it out. Such objects haven't been used yet and are therefore not
+    /// subject to [`PoolConfig::max_uses`].
+    async fn create_idle(&self, timeouts: &Timeouts) -> Result<(), PoolError<M::Error>> {
+        self.acquire_slot(timeouts).await?;
+        let slot_guard = DropGuard(|| self.inner.release_slot());
+        let inner_obj = loop {
+            let mut returned = pin!(self.inner.returned.notified());
+            if let Some(inner_obj) = self.try_create(timeouts, returned.as_mut()).await? {
+                break inner_obj;
+            }
+        };
+        slot_guard.disarm();
+        self.inner.add_idle(inner_obj);
+        Ok(())
+    }
+
+    async fn checkout_with_affinity<K>(
+        &self,
+        key: K,
+        fallback: bool,
+    ) -> Result<W, PoolError<M::Error>>
+    where
+        K: PartialEq + Send + Sync + 'static,
+    {
+        let mut obj = {
+            let predicate = |_: ObjectId, _: &Metrics, extensions: &Extensions| {
+                extensions
+                    .get::<Affinity<K>>()
+                    .is_some_and(|affinity| affinity.0 == key)
+            };
+            self.checkout(&self.timeouts(), Some((&predicate, fallback)), None)
+                .await?
+        };
+        let _ = Object::extensions_mut(&mut obj).insert(Affinity(key));
+        Ok(obj.into())
+    }
+
+    async fn checkout(
+        &self,
+        timeouts: &Timeouts,
+        select: Option<(&SelectFn<'_>, bool)>,
+        deadline: Option<Instant>,
+    ) -> Result<Object<M>, PoolError<M::Error>> {
+        let remaining = deadline.map(|d| d.saturating_duration_since(Instant::now()));
+        let total = match (timeouts.total, remaining) {
+            (Some(total), Some(remaining)) => Some(total.min(remaining)),
+            (total, remaining) => total.or(remaining),
+        };
+        let start = Instant::now();
+        let mut discards = Discards::default();
+        apply_timeout(
+            self.inner.runtime,
+            TimeoutType::Total,
+            total,
+            self.checkout_inner(timeouts, select, &mut discards),
+        )
+        .await
+        .map_err(|e| self.with_context(e, start, discards))
+    }
+
+    /// Attaches the [`ErrorContext`] of a checkout to the given error.
+    fn with_context(
+        &self,
+        mut e: PoolError<M::Error>,
+        start: Instant,
+        discards: Discards<M::Error>,
+    ) -> PoolError<M::Error> {
+        *e.context_mut() = ErrorContext::from_checkout(
+            start.elapsed(),
+            self.status(),
+            discards.count,
+            discards.last_reason,
+            discards.last_recycle_error,
+        );
+        e
+    }
+
+    async fn checkout_inner(
+        &self,
+        timeouts: &Timeouts,
+        select: Option<(&SelectFn<'_>, bool)>,
+        discards: &mut Discards<M::Error>,
+    ) -> Result<Object<M>, PoolError<M::Error>> {
+        self.acquire_slot(timeouts).await?;
+        let slot_guard = DropGuard(|| self.inner.release_slot());
+        let obj = self.fill_slot(timeouts, select, discards).await?;
+        slot_guard.disarm();
+        Ok(obj)
+    }
+
+    /// Waits for a free slot, i.e. a permit of the semaphore, and counts
+    /// the caller as a user. Both must be given back via
+    /// [`PoolInner::release_slot()`] unless the slot is filled with an
+    /// object.
+    async fn acquire_slot(&self, timeouts: &Timeouts) -> Result<(), PoolError<M::Error>> {
+        if let Some(max_waiting) = self.inner.config.max_waiting {
+            // Only futures which would have to wait are rejected.
+            if self.inner.semaphore.available_permits() == 0 && self.status().waiting >= max_waiting
+            {
+                return Err(PoolError::QueueFull(ErrorContext::new()));
+            }
+        }
+        let key = Arc::as_ptr(&self.inner).addr();
+        if self.inner.semaphore.available_permits() == 0 && tracking::holds(key) {
+            return Err(PoolError::NestedCheckout(ErrorContext::new()));
+        }
         let _ = self.inner.users.fetch_add(1, Ordering::Relaxed);
+        self.inner.notify_status();
         let users_guard = DropGuard(|| {
             let _ = self.inner.users.fetch_sub(1, Ordering::Relaxed);
+            self.inner.notify_status();
         });
 
         let non_blocking = match timeouts.wait {
@@ -113,8 +525,10 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
 
         let permit = if non_blocking {
             self.inner.semaphore.try_acquire().map_err(|e| match e {
-                TryAcquireError::Closed => PoolError::Closed,
-                TryAcquireError::NoPermits => PoolError::Timeout(TimeoutType::Wait),
+                TryAcquireError::Closed => PoolError::Closed(ErrorContext::new()),
+                TryAcquireError::NoPermits => {
+                    PoolError::Timeout(TimeoutType::Wait, ErrorContext::new())
+                }
             })?
         } else {
             apply_timeout(
@@ -126,35 +540,46 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
                         .semaphore
                         .acquire()
                         .await
-                        .map_err(|_| PoolError::Closed)
+                        .map_err(|_| PoolError::Closed(ErrorContext::new()))
                 },
             )
             .await?
         };
 
-        let inner_obj = loop {
-            let inner_obj = match self.inner.config.queue_mode {
-                QueueMode::Fifo => self.inner.slots.lock().unwrap().vec.pop_front(),
-                QueueMode::Lifo => self.inner.slots.lock().unwrap().vec.pop_back(),
-            };
+        users_guard.disarm();
+        permit.forget();
+        Ok(())
+    }
+
+    /// Fills a slot acquired via [`Pool::acquire_slot()`] with an idle or
+    /// new object.
+    async fn fill_slot(
+        &self,
+        timeouts: &Timeouts,
+        select: Option<(&SelectFn<'_>, bool)>,
+        discards: &mut Discards<M::Error>,
+    ) -> Result<Object<M>, PoolError<M::Error>> {
+        let mut inner_obj = loop {
+            // This is created before looking for an idle object so objects
+            // returned while waiting for the budget aren't missed.
+            let mut returned = pin!(self.inner.returned.notified());
+            let inner_obj = self.inner.pop_idle(select);
             let inner_obj = if let Some(inner_obj) = inner_obj {
-                self.try_recycle(timeouts, inner_obj).await?
+                self.try_recycle(timeouts, inner_obj, discards).await?
             } else {
-                self.try_create(timeouts).await?
+                self.try_create(timeouts, returned.as_mut()).await?
             };
             if let Some(inner_obj) = inner_obj {
                 break inner_obj;
             }
         };
-
-        users_guard.disarm();
-        permit.forget();
+        inner_obj.checkouts += 1;
 
         Ok(Object {
             inner: Some(inner_obj),
             pool: self.weak(),
-        }
-        .into())
+            _checkout: tracking::record(Arc::as_ptr(&self.inner).addr()),
+        })
     }
 
     #[inline]
@@ -162,6 +587,7 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         &self,
         timeouts: &Timeouts,
         inner_obj: ObjectInner<M>,
+        discards: &mut Discards<M::Error>,
     ) -> Result<Option<ObjectInner<M>>, PoolError<M::Error>> {
         let mut unready_obj = UnreadyObject {
             inner: Some(inner_obj),
@@ -169,35 +595,64 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         };
         let inner = unready_obj.inner();
 
+        if self.inner.is_invalidated(inner) {
+            self.inner.report_discard(inner, DiscardReason::Invalidated);
+            discards.record(DiscardReason::Invalidated, None);
+            return Ok(None);
+        }
+
         // Apply pre_recycle hooks
         if let Err(_e) = self.inner.hooks.pre_recycle.apply(inner).await {
             // TODO log pre_recycle error
+            self.inner
+                .report_discard(inner, DiscardReason::PreRecycleHookFailed);
+            discards.record(DiscardReason::PreRecycleHookFailed, None);
             return Ok(None);
         }
 
-        if apply_timeout(
-            self.inner.runtime,
-            TimeoutType::Recycle,
-            timeouts.recycle,
-            self.inner.manager.recycle(&mut inner.obj, &inner.metrics),
-        )
-        .await
-        .is_err()
-        {
-            return Ok(None);
+        // Objects which were validated recently are handed out without
+        // asking the manager.
+        let skip_check = self
+            .inner
+            .config
+            .recycle_check_interval
+            .is_some_and(|interval| inner.validated.elapsed() < interval);
+        if !skip_check {
+            if let Err(e) = apply_timeout(
+                self.inner.runtime,
+                TimeoutType::Recycle,
+                timeouts.recycle,
+                inner.manager.recycle_with_extensions(
+                    &mut inner.obj,
+                    &inner.metrics,
+                    &mut inner.extensions,
+                ),
+            )
+            .await
+            {
+                let (reason, error) = match e {
+                    PoolError::Backend(e, _) => (DiscardReason::RecycleFailed, Some(e)),
+                    PoolError::Timeout(..) => (DiscardReason::RecycleTimeout, None),
+                    _ => (DiscardReason::RecycleFailed, None),
+                };
+                self.inner.report_discard(inner, reason);
+                discards.record(reason, error);
+                return Ok(None);
+            }
+            inner.validated = Instant::now();
         }
 
         // Apply post_recycle hooks
         if let Err(_e) = self.inner.hooks.post_recycle.apply(inner).await {
             // TODO log post_recycle error
+            self.inner
+                .report_discard(inner, DiscardReason::PostRecycleHookFailed);
+            discards.record(DiscardReason::PostRecycleHookFailed, None);
             return Ok(None);
         }
 
         inner.metrics.recycle_count += 1;
-        #[cfg(not(target_arch = "wasm32"))]
-        {
-            inner.metrics.recycled = Some(Instant::now());
-        }
+        inner.metrics.recycled = Some(Instant::now());
 
         Ok(Some(unready_obj.ready()))
     }
@@ -206,23 +661,52 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     async fn try_create(
         &self,
         timeouts: &Timeouts,
+        returned: Pin<&mut Notified<'_>>,
     ) -> Result<Option<ObjectInner<M>>, PoolError<M::Error>> {
+        // The generation is read before creating the object so objects
+        // which are being created while the pool is invalidated are
+        // treated as invalidated, too.
+        let (manager, generation) = self.inner.current_manager();
+        let budget_permit = match &self.inner.budget {
+            Some(budget) => {
+                let permit = apply_timeout(
+                    self.inner.runtime,
+                    TimeoutType::Budget,
+                    timeouts.wait,
+                    async { Ok::<_, PoolError<M::Error>>(acquire_budget(budget, returned).await) },
+                )
+                .await?;
+                // An object was returned to this pool which is used instead.
+                let Some(permit) = permit else {
+                    return Ok(None);
+                };
+                Some(permit)
+            }
+            None => None,
+        };
         let mut unready_obj = UnreadyObject {
             inner: Some(ObjectInner {
                 obj: apply_timeout(
                     self.inner.runtime,
                     TimeoutType::Create,
                     timeouts.create,
-                    self.inner.manager.create(),
+                    manager.create(),
                 )
                 .await?,
                 id: self.inner.next_id.fetch_add(1, Ordering::Relaxed),
+                generation,
+                manager,
                 metrics: Metrics::default(),
+                extensions: Extensions::default(),
+                validated: Instant::now(),
+                checkouts: 0,
+                _budget_permit: budget_permit,
             }),
             pool: &self.inner,
         };
 
         self.inner.slots.lock().unwrap().size += 1;
+        self.inner.notify_status();
 
         // Apply post_create hooks
         if let Err(e) = self
@@ -232,7 +716,7 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
             .apply(unready_obj.inner())
             .await
         {
-            return Err(PoolError::PostCreateHook(e));
+            return Err(PoolError::PostCreateHook(e, ErrorContext::new()));
         }
 
         Ok(Some(unready_obj.ready()))
@@ -249,6 +733,7 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
         if self.inner.semaphore.is_closed() {
             return;
         }
+        let mut removed = Vec::new();
         let mut slots = self.inner.slots.lock().unwrap();
         let old_max_size = slots.max_size;
         slots.max_size = max_size;
@@ -257,8 +742,9 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
             while slots.size > slots.max_size {
                 if let Ok(permit) = self.inner.semaphore.try_acquire() {
                     permit.forget();
-                    if slots.vec.pop_front().is_some() {
+                    if let Some(obj) = slots.vec.pop_front() {
                         slots.size -= 1;
+                        removed.push(obj);
                     }
                 } else {
                     break;
@@ -277,6 +763,11 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
             slots.vec.reserve_exact(additional);
             self.inner.semaphore.add_permits(additional);
         }
+        drop(slots);
+        self.inner.notify_status();
+        for obj in removed {
+            self.inner.report_discard(&obj, DiscardReason::Resized);
+        }
     }
 
     /// Retains only the objects specified by the given function.
@@ -317,22 +808,92 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
                 i += 1;
             } else {
                 let mut obj = guard.vec.remove(i).unwrap();
-                self.manager().detach(&mut obj.obj);
+                obj.manager.detach(&mut obj.obj);
                 removed.push(obj.obj);
             }
         }
         guard.size -= removed.len();
+        drop(guard);
+        self.inner.notify_status();
         RetainResult {
             retained: i,
             removed,
         }
     }
 
+    /// Invalidates all objects of this [`Pool`].
+    ///
+    /// Idle objects are removed from the [`Pool`] immediately. Objects which
+    /// are currently checked out are discarded once they are returned to the
+    /// [`Pool`] instead of being reused. Objects created after calling this
+    /// method are not affected.
+    ///
+    /// This is typically used after a credential rotation or a schema
+    /// migration which requires all existing connections to be replaced.
+    pub fn invalidate(&self) {
+        self.inner.invalidate_all();
+        self.discard_invalidated();
+    }
+
+    /// Invalidates all objects of this [`Pool`] matching the given
+    /// `predicate`.
+    ///
+    /// This works like [`Pool::invalidate()`] but only discards objects for
+    /// which the `predicate` returns `true`. Idle objects are checked
+    /// immediately. Objects which are currently checked out are checked once
+    /// they are returned to the [`Pool`] or before they are recycled.
+    ///
+    /// The `predicate` is kept until all objects created before calling
+    /// this method are gone or until the next call of [`Pool::invalidate()`].
+    /// It must not block.
+    pub fn invalidate_where(
+        &self,
+        predicate: impl Fn(ObjectId, Metrics) -> bool + Send + Sync + 'static,
+    ) {
+        let mut invalidations = self.inner.invalidations.lock().unwrap();
+        let generation = self.inner.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        invalidations
+            .predicates
+            .push((generation, Box::new(predicate)));
+        invalidations.prune();
+        drop(invalidations);
+        self.discard_invalidated();
+    }
+
+    /// Removes all idle objects which have been invalidated.
+    fn discard_invalidated(&self) {
+        let mut removed = Vec::new();
+        let mut slots = self.inner.slots.lock().unwrap();
+        let mut i = 0;
+        while i < slots.vec.len() {
+            if self.inner.is_invalidated(&slots.vec[i]) {
+                let mut obj = slots.vec.remove(i).unwrap();
+                obj.manager.detach(&mut obj.obj);
+                removed.push(obj);
+            } else {
+                i += 1;
+            }
+        }
+        slots.size -= removed.len();
+        drop(slots);
+        self.inner.notify_status();
+        for obj in &removed {
+            self.inner.report_discard(obj, DiscardReason::Invalidated);
+        }
+        // Dropping the objects might be expensive which is why this
+        // is done after releasing the lock.
+        drop(removed);
+    }
+
     /// Get current timeout configuration
     pub fn timeouts(&self) -> Timeouts {
         self.inner.config.timeouts
     }
 
+    pub(crate) fn runtime(&self) -> Option<Runtime> {
+        self.inner.runtime
+    }
+
     /// Closes this [`Pool`].
     ///
     /// All current and future tasks waiting for [`Object`]s will return
@@ -342,6 +903,12 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     pub fn close(&self) {
         self.resize(0);
         self.inner.semaphore.close();
+        // Receivers are notified even if the status didn't change so
+        // `Pool::wait_for_available()` can return.
+        let status = self.status();
+        self.inner
+            .status_tx
+            .send_modify(|current| *current = status);
     }
 
     /// Indicates whether this [`Pool`] has been closed.
@@ -352,25 +919,76 @@ impl<M: Manager, W: From<Object<M>>> Pool<M, W> {
     /// Retrieves [`Status`] of this [`Pool`].
     #[must_use]
     pub fn status(&self) -> Status {
-        let slots = self.inner.slots.lock().unwrap();
-        let users = self.inner.users.load(Ordering::Relaxed);
-        let (available, waiting) = if users < slots.size {
-            (slots.size - users, 0)
-        } else {
-            (0, users - slots.size)
-        };
-        Status {
-            max_size: slots.max_size,
-            size: slots.size,
-            available,
-            waiting,
+        self.inner.status()
+    }
+
+    /// Returns a [`watch::Receiver`] which is updated whenever the
+    /// [`Status`] of this [`Pool`] changes.
+    ///
+    /// This is useful for readiness probes and autoscalers which would
+    /// otherwise poll [`Pool::status()`]. Just like [`Pool::status()`] the
+    /// published values are not guaranteed to be consistent under heavy
+    /// load.
+    pub fn watch_status(&self) -> watch::Receiver<Status> {
+        let receiver = self.inner.status_tx.subscribe();
+        // The status isn't tracked while there are no receivers.
+        self.inner.notify_status();
+        receiver
+    }
+
+    /// Waits until at least `n` idle objects are available.
+    ///
+    /// This is useful during warm-up, e.g. together with
+    /// [`Pool::warm_up()`], for delaying readiness until the [`Pool`] is
+    /// able to serve requests.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`PoolError::Closed`] if this [`Pool`] has been or is being
+    /// closed.
+    pub async fn wait_for_available(&self, n: usize) -> Result<(), PoolError<M::Error>> {
+        let mut receiver = self.watch_status();
+        // The sender is owned by the pool which outlives this future.
+        let _ = receiver
+            .wait_for(|status| status.available >= n || self.is_closed())
+            .await;
+        if self.is_closed() {
+            return Err(PoolError::Closed(ErrorContext::new()));
         }
+        Ok(())
     }
 
-    /// Returns [`Manager`] of this [`Pool`].
+    /// Returns the current [`Manager`] of this [`Pool`].
+    ///
+    /// The [`Manager`] can be replaced at any time via
+    /// [`Pool::replace_manager()`] which is why a shared reference to it is
+    /// returned.
     #[must_use]
-    pub fn manager(&self) -> &M {
-        &self.inner.manager
+    pub fn manager(&self) -> Arc<M> {
+        self.inner.manager.lock().unwrap().clone()
+    }
+
+    /// Replaces the [`Manager`] of this [`Pool`].
+    ///
+    /// All objects created after calling this method are created by the new
+    /// [`Manager`]. Objects created by the old [`Manager`] keep being
+    /// recycled and detached by it until they are retired according to the
+    /// given [`RetirePolicy`]. Futures waiting for an [`Object`] are not
+    /// affected by this method.
+    ///
+    /// This can be used to rotate credentials or move to a different host
+    /// without having to replace every clone of the [`Pool`].
+    pub fn replace_manager(&self, manager: M, policy: RetirePolicy) {
+        let mut current = self.inner.manager.lock().unwrap();
+        *current = Arc::new(manager);
+        // The generation is bumped while holding the lock so the new
+        // manager is never paired with an old generation.
+        self.inner.invalidate_all();
+        drop(current);
+        match policy {
+            RetirePolicy::Immediate => self.discard_invalidated(),
+            RetirePolicy::Gradual => {}
+        }
     }
 
     /// Returns a [`WeakPool<T>`] of this [`Pool`].
@@ -412,17 +1030,197 @@ impl<M: Manager, W: From<Object<M>>> WeakPool<M, W> {
     }
 }
 
+/// Slot of a [`Pool`] reserved via [`Pool::reserve()`].
+///
+/// The slot is given back to the [`Pool`] when the [`Reservation`] is
+/// dropped without being turned into an [`Object`].
+#[must_use]
+pub struct Reservation<M: Manager, W: From<Object<M>> = Object<M>> {
+    pool: Pool<M, W>,
+    reserved: bool,
+}
+
+impl<M: Manager, W: From<Object<M>>> Reservation<M, W> {
+    /// Turns this [`Reservation`] into an [`Object`] by recycling an idle
+    /// object or creating a new one.
+    ///
+    /// This never waits for a slot. Only the configured
+    /// [`Timeouts::create`] and [`Timeouts::recycle`] apply.
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details. The slot is given back to the
+    /// [`Pool`] if retrieving an [`Object`] fails.
+    pub async fn get(mut self) -> Result<W, PoolError<M::Error>> {
+        self.reserved = false;
+        let inner = &self.pool.inner;
+        let _ = inner.users.fetch_add(1, Ordering::Relaxed);
+        let _ = inner.reserved.fetch_sub(1, Ordering::Relaxed);
+        inner.notify_status();
+        let slot_guard = DropGuard(|| inner.release_slot());
+        let timeouts = self.pool.timeouts();
+        let start = Instant::now();
+        let mut discards = Discards::default();
+        let obj = self
+            .pool
+            .fill_slot(&timeouts, None, &mut discards)
+            .await
+            .map_err(|e| self.pool.with_context(e, start, discards))?;
+        slot_guard.disarm();
+        Ok(obj.into())
+    }
+
+    /// Returns the [`Pool`] this [`Reservation`] belongs to.
+    #[must_use]
+    pub fn pool(&self) -> &Pool<M, W> {
+        &self.pool
+    }
+}
+
+impl<M: Manager, W: From<Object<M>>> Drop for Reservation<M, W> {
+    fn drop(&mut self) {
+        if self.reserved {
+            let inner = &self.pool.inner;
+            let _ = inner.reserved.fetch_sub(1, Ordering::Relaxed);
+            inner.semaphore.add_permits(1);
+            inner.notify_status();
+        }
+    }
+}
+
+impl<M, W> fmt::Debug for Reservation<M, W>
+where
+    M: Manager,
+    W: From<Object<M>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Reservation")
+            .field("reserved", &self.reserved)
+            .finish_non_exhaustive()
+    }
+}
+
 pub(crate) struct PoolInner<M: Manager> {
-    manager: M,
+    /// The current manager. Objects keep a reference to the manager which
+    /// created them.
+    manager: Mutex<Arc<M>>,
     next_id: AtomicUsize,
+    /// Current generation of the [`Pool`]. This is incremented each time
+    /// objects are invalidated.
+    generation: AtomicUsize,
+    invalidations: Arc<Mutex<Invalidations>>,
     slots: Mutex<Slots<ObjectInner<M>>>,
     /// Number of [`Pool`] users. A user is both a future which is waiting for an [`Object`] or one
     /// with an [`Object`] which hasn't been returned, yet.
     users: AtomicUsize,
+    /// Number of slots held by a [`Reservation`]. They are neither counted
+    /// as users nor as objects.
+    reserved: AtomicUsize,
     semaphore: Semaphore,
     config: PoolConfig,
     runtime: Option<Runtime>,
     hooks: Hooks<M>,
+    budget: Option<Budget>,
+    /// Notified whenever an object becomes idle.
+    returned: Notify,
+    /// Sender of [`Pool::watch_status()`].
+    status_tx: watch::Sender<Status>,
+}
+
+/// Objects discarded while retrieving an object. This is used for
+/// providing an [`ErrorContext`] if the checkout fails.
+struct Discards<E> {
+    count: usize,
+    last_reason: Option<DiscardReason>,
+    last_recycle_error: Option<RecycleError<E>>,
+}
+
+impl<E> Default for Discards<E> {
+    fn default() -> Self {
+        Self {
+            count: 0,
+            last_reason: None,
+            last_recycle_error: None,
+        }
+    }
+}
+
+impl<E> Discards<E> {
+    fn record(&mut self, reason: DiscardReason, error: Option<RecycleError<E>>) {
+        self.count += 1;
+        self.last_reason = Some(reason);
+        if error.is_some() {
+            self.last_recycle_error = error;
+        }
+    }
+}
+
+/// Function signature for predicates selecting idle objects
+type SelectFn<'a> = dyn Fn(ObjectId, &Metrics, &Extensions) -> bool + Sync + 'a;
+
+/// Function signature for predicates passed to [`Pool::invalidate_where()`]
+type InvalidateFn = dyn Fn(ObjectId, Metrics) -> bool + Send + Sync;
+
+#[derive(Default)]
+pub(crate) struct Invalidations {
+    /// Objects of a generation lower than this are invalid.
+    min_generation: usize,
+    /// Objects of a generation lower than the one stored alongside the
+    /// predicate are invalid if the predicate returns `true`.
+    predicates: Vec<(usize, Box<InvalidateFn>)>,
+    /// Number of live objects per generation.
+    live: BTreeMap<usize, usize>,
+}
+
+impl Invalidations {
+    /// Removes the predicates which can't match any live object as all
+    /// of them are of the same or a newer generation.
+    fn prune(&mut self) {
+        match self.live.keys().next() {
+            Some(&oldest) => self
+                .predicates
+                .retain(|(generation, _)| *generation > oldest),
+            // Objects created from now on never match any predicate.
+            None => self.predicates.clear(),
+        }
+    }
+}
+
+// Implemented manually as the predicates don't implement `Debug`.
+impl fmt::Debug for Invalidations {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Invalidations")
+            .field("min_generation", &self.min_generation)
+            .field("predicates", &self.predicates.len())
+            .field("live", &self.live)
+            .finish()
+    }
+}
+
+/// Generation of an object. The object is counted as live object of its
+/// generation until this is dropped.
+pub(crate) struct Generation {
+    value: usize,
+    invalidations: Arc<Mutex<Invalidations>>,
+}
+
+impl Drop for Generation {
+    fn drop(&mut self) {
+        let mut invalidations = self.invalidations.lock().unwrap();
+        if let Some(count) = invalidations.live.get_mut(&self.value) {
+            *count -= 1;
+            if *count == 0 {
+                let _ = invalidations.live.remove(&self.value);
+                invalidations.prune();
+            }
+        }
+    }
+}
+
+impl fmt::Debug for Generation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        fmt::Debug::fmt(&self.value, f)
+    }
 }
 
 #[derive(Debug)]
@@ -441,8 +1239,11 @@ where
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         f.debug_struct("PoolInner")
             .field("manager", &self.manager)
+            .field("generation", &self.generation)
+            .field("invalidations", &self.invalidations)
             .field("slots", &self.slots)
             .field("used", &self.users)
+            .field("reserved", &self.reserved)
             .field("semaphore", &self.semaphore)
             .field("config", &self.config)
             .field("runtime", &self.runtime)
@@ -453,19 +1254,65 @@ where
 
 impl<M: Manager> PoolInner<M> {
     pub(crate) fn return_object(&self, mut inner: ObjectInner<M>) {
+        if self.is_invalidated(&inner) {
+            self.report_discard(&inner, DiscardReason::Invalidated);
+            self.detach_object(&mut inner);
+            return;
+        }
+        if let Some(max_uses) = self.config.max_uses {
+            if inner.checkouts >= max_uses {
+                self.report_discard(&inner, DiscardReason::MaxUsesReached);
+                self.detach_object(&mut inner);
+                return;
+            }
+        }
+        if let Some(budget) = &self.budget {
+            if budget.has_waiters() && !self.has_waiters() {
+                self.report_discard(&inner, DiscardReason::Reclaimed);
+                self.detach_object(&mut inner);
+                return;
+            }
+        }
+        self.add_idle(inner);
+    }
+
+    /// Adds an object whose slot is still held to the idle objects and
+    /// releases the slot.
+    fn add_idle(&self, mut inner: ObjectInner<M>) {
         let _ = self.users.fetch_sub(1, Ordering::Relaxed);
         let mut slots = self.slots.lock().unwrap();
         if slots.size <= slots.max_size {
             slots.vec.push_back(inner);
             drop(slots);
             self.semaphore.add_permits(1);
+            if self.budget.is_some() {
+                self.returned.notify_waiters();
+            }
+            self.notify_status();
         } else {
             slots.size -= 1;
             drop(slots);
-            self.manager.detach(&mut inner.obj);
+            self.notify_status();
+            self.report_discard(&inner, DiscardReason::Resized);
+            inner.manager.detach(&mut inner.obj);
         }
     }
-    pub(crate) fn detach_object(&self, obj: &mut M::Type) {
+    /// Applies the `pre_return` hooks to an object being released via
+    /// [`Object::release()`].
+    pub(crate) async fn pre_return(
+        &self,
+        inner: &mut ObjectInner<M>,
+    ) -> Result<(), HookError<M::Error>> {
+        self.hooks.pre_return.apply(inner).await
+    }
+    /// Gives back a slot acquired via [`Pool::acquire_slot()`] which wasn't
+    /// filled with an object.
+    fn release_slot(&self) {
+        let _ = self.users.fetch_sub(1, Ordering::Relaxed);
+        self.semaphore.add_permits(1);
+        self.notify_status();
+    }
+    pub(crate) fn detach_object(&self, inner: &mut ObjectInner<M>) {
         let _ = self.users.fetch_sub(1, Ordering::Relaxed);
         let mut slots = self.slots.lock().unwrap();
         let add_permits = slots.size <= slots.max_size;
@@ -474,7 +1321,129 @@ impl<M: Manager> PoolInner<M> {
         if add_permits {
             self.semaphore.add_permits(1);
         }
-        self.manager.detach(obj);
+        self.notify_status();
+        inner.manager.detach(&mut inner.obj);
+    }
+    /// Removes an idle object from the queue.
+    ///
+    /// If a predicate is given only matching objects are returned. Unless
+    /// `fallback` is set a non matching object is discarded in order to
+    /// make room for a new one if the pool is full.
+    fn pop_idle(&self, select: Option<(&SelectFn<'_>, bool)>) -> Option<ObjectInner<M>> {
+        let queue_mode = self.config.queue_mode;
+        let mut slots = self.slots.lock().unwrap();
+        let pop = |vec: &mut VecDeque<ObjectInner<M>>| match queue_mode {
+            QueueMode::Fifo => vec.pop_front(),
+            QueueMode::Lifo => vec.pop_back(),
+        };
+        let Some((predicate, fallback)) = select else {
+            return pop(&mut slots.vec);
+        };
+        let matches =
+            |obj: &ObjectInner<M>| predicate(ObjectId(obj.id), &obj.metrics, &obj.extensions);
+        let index = match queue_mode {
+            QueueMode::Fifo => slots.vec.iter().position(matches),
+            QueueMode::Lifo => slots.vec.iter().rposition(matches),
+        };
+        if let Some(index) = index {
+            return slots.vec.remove(index);
+        }
+        if fallback {
+            return pop(&mut slots.vec);
+        }
+        if slots.size < slots.max_size {
+            return None;
+        }
+        // The least recently used object is at the front of the queue.
+        let mut evicted = slots.vec.pop_front()?;
+        slots.size -= 1;
+        drop(slots);
+        self.notify_status();
+        self.report_discard(&evicted, DiscardReason::Evicted);
+        evicted.manager.detach(&mut evicted.obj);
+        None
+    }
+    fn status(&self) -> Status {
+        let slots = self.slots.lock().unwrap();
+        let users = self.users.load(Ordering::Relaxed);
+        let reserved = self.reserved.load(Ordering::Relaxed);
+        let (available, waiting) = if users < slots.size {
+            // Idle objects are kept for the reservations.
+            ((slots.size - users).saturating_sub(reserved), 0)
+        } else {
+            (0, users - slots.size)
+        };
+        Status {
+            max_size: slots.max_size,
+            size: slots.size,
+            available,
+            waiting,
+        }
+    }
+    /// Publishes the current [`Status`] to the receivers of
+    /// [`Pool::watch_status()`].
+    ///
+    /// This must not be called while holding the lock of the slots.
+    fn notify_status(&self) {
+        if self.status_tx.receiver_count() == 0 {
+            return;
+        }
+        let status = self.status();
+        let _ = self.status_tx.send_if_modified(|current| {
+            let modified = *current != status;
+            *current = status;
+            modified
+        });
+    }
+    /// Checks whether there are users waiting for an object.
+    fn has_waiters(&self) -> bool {
+        let slots = self.slots.lock().unwrap();
+        let checked_out = slots.size - slots.vec.len();
+        self.users.load(Ordering::Relaxed) > checked_out
+    }
+    /// Calls the `on_discard` callbacks for the given object.
+    pub(crate) fn report_discard(&self, inner: &ObjectInner<M>, reason: DiscardReason) {
+        self.hooks
+            .on_discard
+            .apply(ObjectId(inner.id), &inner.metrics, reason);
+    }
+    /// Returns the current manager and generation. The returned
+    /// [`Generation`] counts as live object until it is dropped.
+    fn current_manager(&self) -> (Arc<M>, Generation) {
+        let manager = self.manager.lock().unwrap();
+        let mut invalidations = self.invalidations.lock().unwrap();
+        let value = self.generation.load(Ordering::Relaxed);
+        *invalidations.live.entry(value).or_default() += 1;
+        drop(invalidations);
+        let generation = Generation {
+            value,
+            invalidations: self.invalidations.clone(),
+        };
+        (manager.clone(), generation)
+    }
+    /// Invalidates all objects created so far.
+    fn invalidate_all(&self) {
+        let mut invalidations = self.invalidations.lock().unwrap();
+        let generation = self.generation.fetch_add(1, Ordering::Relaxed) + 1;
+        invalidations.min_generation = generation;
+        // All objects matched by previous predicates are invalidated anyways.
+        invalidations.predicates.clear();
+    }
+    /// Checks whether the given object has been invalidated via
+    /// [`Pool::invalidate()`] or [`Pool::invalidate_where()`].
+    fn is_invalidated(&self, inner: &ObjectInner<M>) -> bool {
+        let value = inner.generation.value;
+        if value == self.generation.load(Ordering::Relaxed) {
+            return false;
+        }
+        let invalidations = self.invalidations.lock().unwrap();
+        value < invalidations.min_generation
+            || invalidations
+                .predicates
+                .iter()
+                .any(|(generation, predicate)| {
+                    value < *generation && predicate(ObjectId(inner.id), inner.metrics)
+                })
     }
 }
 
@@ -496,12 +1465,49 @@ impl<M: Manager> Drop for UnreadyObject<'_, M> {
     fn drop(&mut self) {
         if let Some(mut inner) = self.inner.take() {
             self.pool.slots.lock().unwrap().size -= 1;
-            self.pool.manager.detach(&mut inner.obj);
+            self.pool.notify_status();
+            inner.manager.detach(&mut inner.obj);
         }
     }
 }
 
-async fn apply_timeout<O, E>(
+impl<M: Manager> Reclaim for PoolInner<M> {
+    fn idle(&self) -> usize {
+        self.slots.lock().unwrap().vec.len()
+    }
+
+    fn reclaim_idle(&self) -> bool {
+        let mut slots = self.slots.lock().unwrap();
+        // The least recently used object is at the front of the queue.
+        let Some(mut reclaimed) = slots.vec.pop_front() else {
+            return false;
+        };
+        slots.size -= 1;
+        drop(slots);
+        self.notify_status();
+        self.report_discard(&reclaimed, DiscardReason::Reclaimed);
+        reclaimed.manager.detach(&mut reclaimed.obj);
+        true
+    }
+}
+
+/// Acquires a permit of the `budget` unless `returned` completes first, i.e.
+/// an object was returned to the [`Pool`] in the meantime.
+async fn acquire_budget(
+    budget: &Budget,
+    mut returned: Pin<&mut Notified<'_>>,
+) -> Option<BudgetPermit> {
+    let mut acquire = pin!(budget.acquire());
+    poll_fn(|cx| {
+        if let Poll::Ready(permit) = acquire.as_mut().poll(cx) {
+            return Poll::Ready(Some(permit));
+        }
+        returned.as_mut().poll(cx).map(|()| None)
+    })
+    .await
+}
+
+pub(crate) async fn apply_timeout<O, E>(
     runtime: Option<Runtime>,
     timeout_type: TimeoutType,
     duration: Option<Duration>,
@@ -511,12 +1517,27 @@ async fn apply_timeout<O, E>(
         (_, None) => future.await.map_err(Into::into),
         (Some(runtime), Some(duration)) => timeout(runtime, duration, future)
             .await
-            .ok_or(PoolError::Timeout(timeout_type))?
+            .ok_or(PoolError::Timeout(timeout_type, ErrorContext::new()))?
             .map_err(Into::into),
-        (None, Some(_)) => Err(PoolError::NoRuntimeSpecified),
+        (None, Some(_)) => Err(PoolError::NoRuntimeSpecified(ErrorContext::new())),
     }
 }
 
+/// Policy for retiring objects created by a previous [`Manager`] when
+/// calling [`Pool::replace_manager()`].
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum RetirePolicy {
+    /// Idle objects are removed from the [`Pool`] immediately. Objects which
+    /// are currently checked out are discarded once they are returned. This
+    /// is the same as calling [`Pool::invalidate()`].
+    #[default]
+    Immediate,
+    /// Objects are discarded one by one once they are returned to the
+    /// [`Pool`] or are about to be recycled. This replaces the objects at the
+    /// pace at which they are used.
+    Gradual,
+}
+
 #[derive(Debug)]
 /// This is the result returned by `Pool::retain`
 pub struct RetainResult<T> {
@@ -16,7 +16,10 @@
 
 pub use crate::{
     Runtime,
-    managed::{Metrics, ObjectId, PoolConfig, Status, TimeoutType, Timeouts},
+    managed::{
+        Affinity, DiscardReason, ErrorContext, Extensions, Metrics, ObjectId, PoolConfig,
+        RetirePolicy, RetryPolicy, Status, TimeoutType, Timeouts,
+    },
 };
 
 /// This macro creates all the type aliases usually reexported by
@@ -33,6 +36,9 @@ macro_rules! managed_reexports {
         #[doc=concat!("Type alias for using [`deadpool::managed::Pool`] with [`", $crate_name, "`].")]
         pub type WeakPool = deadpool::managed::WeakPool<$Manager, $Wrapper>;
 
+        #[doc=concat!("Type alias for using [`deadpool::managed::Reservation`] with [`", $crate_name, "`].")]
+        pub type Reservation = deadpool::managed::Reservation<$Manager, $Wrapper>;
+
         #[doc=concat!("Type alias for using [`deadpool::managed::PoolBuilder`] with [`", $crate_name, "`].")]
         pub type PoolBuilder = deadpool::managed::PoolBuilder<$Manager, $Wrapper>;
 
@@ -42,6 +48,9 @@ macro_rules! managed_reexports {
         #[doc=concat!("Type alias for using [`deadpool::managed::CreatePoolError`] with [`", $crate_name, "`].")]
         pub type CreatePoolError = deadpool::managed::CreatePoolError<$ConfigError>;
 
+        #[doc=concat!("Type alias for using [`deadpool::managed::WarmUpError`] with [`", $crate_name, "`].")]
+        pub type WarmUpError = deadpool::managed::WarmUpError<$Error>;
+
         #[doc=concat!("Type alias for using [`deadpool::managed::PoolError`] with [`", $crate_name, "`].")]
         pub type PoolError = deadpool::managed::PoolError<$Error>;
 
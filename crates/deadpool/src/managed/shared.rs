@@ -0,0 +1,275 @@
+//! Pool handing out objects to multiple concurrent borrowers.
+
+use std::{
+    fmt,
+    ops::Deref,
+    sync::{Arc, Mutex, Weak},
+};
+
+use tokio::sync::{Mutex as AsyncMutex, Semaphore};
+
+use super::{
+    ErrorContext, Manager, Object, ObjectId, Pool, PoolError, TimeoutType, pool::apply_timeout,
+};
+
+/// Pool leasing every object to up to `max_borrowers` concurrent borrowers.
+///
+/// This is useful for objects which can serve many callers at once, e.g.
+/// multiplexed connections or HTTP/2 channels. The objects are retrieved
+/// from the wrapped [`Pool`] which is also responsible for creating and
+/// recycling them.
+///
+/// A new object is only retrieved from the wrapped [`Pool`] if all objects
+/// are leased to `max_borrowers` borrowers. Otherwise the least loaded
+/// object is leased. Once an object isn't leased to anyone anymore it is
+/// returned to the wrapped [`Pool`].
+///
+/// The wrapped [`Pool`] should not be used by anything else. The capacity
+/// of a [`SharedPool`] is determined once when creating it, i.e. resizing
+/// the wrapped [`Pool`] afterwards isn't reflected.
+pub struct SharedPool<M: Manager> {
+    inner: Arc<SharedPoolInner<M>>,
+}
+
+struct SharedPoolInner<M: Manager> {
+    pool: Pool<M>,
+    max_borrowers: usize,
+    /// Every lease holds one permit of this semaphore.
+    semaphore: Semaphore,
+    entries: Mutex<Vec<Entry<M>>>,
+    /// Held while retrieving a new object so concurrent callers lease the
+    /// new object instead of retrieving one, too.
+    create_lock: AsyncMutex<()>,
+}
+
+struct Entry<M: Manager> {
+    obj: Arc<Object<M>>,
+    leases: usize,
+}
+
+impl<M: Manager> Clone for SharedPool<M> {
+    fn clone(&self) -> Self {
+        Self {
+            inner: self.inner.clone(),
+        }
+    }
+}
+
+impl<M: Manager> fmt::Debug for SharedPool<M> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedPool")
+            .field("status", &self.pool().status())
+            .field("max_borrowers", &self.inner.max_borrowers)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<M: Manager> SharedPool<M> {
+    /// Creates a new [`SharedPool`] leasing every object of the given
+    /// [`Pool`] to up to `max_borrowers` concurrent borrowers.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `max_borrowers` is `0`.
+    #[must_use]
+    pub fn new(pool: Pool<M>, max_borrowers: usize) -> Self {
+        assert!(max_borrowers > 0, "max_borrowers must be greater than 0");
+        let permits = pool
+            .status()
+            .max_size
+            .saturating_mul(max_borrowers)
+            .min(Semaphore::MAX_PERMITS);
+        Self {
+            inner: Arc::new(SharedPoolInner {
+                pool,
+                max_borrowers,
+                semaphore: Semaphore::new(permits),
+                entries: Mutex::new(Vec::new()),
+                create_lock: AsyncMutex::new(()),
+            }),
+        }
+    }
+
+    /// Leases an object to the caller or waits for one to become available.
+    ///
+    /// The configured [`Timeouts::wait`] applies to waiting for a lease.
+    /// Retrieving a new object from the wrapped [`Pool`] uses its
+    /// configured [`Timeouts`].
+    ///
+    /// # Errors
+    ///
+    /// See [`PoolError`] for details.
+    ///
+    /// [`Timeouts`]: super::Timeouts
+    /// [`Timeouts::wait`]: super::Timeouts::wait
+    pub async fn get(&self) -> Result<SharedObject<M>, PoolError<M::Error>> {
+        let inner = &self.inner;
+        let permit = apply_timeout(
+            inner.pool.runtime(),
+            TimeoutType::Wait,
+            inner.pool.timeouts().wait,
+            async {
+                inner
+                    .semaphore
+                    .acquire()
+                    .await
+                    .map_err(|_| PoolError::Closed(ErrorContext::new()))
+            },
+        )
+        .await?;
+
+        let obj = match self.lease() {
+            Some(obj) => obj,
+            None => {
+                let _create_guard = inner.create_lock.lock().await;
+                match self.lease() {
+                    Some(obj) => obj,
+                    None => {
+                        let obj = Arc::new(inner.pool.get().await?);
+                        inner.entries.lock().unwrap().push(Entry {
+                            obj: obj.clone(),
+                            leases: 1,
+                        });
+                        obj
+                    }
+                }
+            }
+        };
+
+        permit.forget();
+        Ok(SharedObject {
+            lease: Arc::new(Lease {
+                obj,
+                pool: Arc::downgrade(inner),
+            }),
+        })
+    }
+
+    /// Leases the least loaded object which isn't leased to `max_borrowers`
+    /// borrowers, yet.
+    fn lease(&self) -> Option<Arc<Object<M>>> {
+        let mut entries = self.inner.entries.lock().unwrap();
+        let entry = entries
+            .iter_mut()
+            .filter(|entry| entry.leases < self.inner.max_borrowers)
+            .min_by_key(|entry| entry.leases)?;
+        entry.leases += 1;
+        Some(entry.obj.clone())
+    }
+
+    /// Returns the wrapped [`Pool`].
+    #[must_use]
+    pub fn pool(&self) -> &Pool<M> {
+        &self.inner.pool
+    }
+
+    /// Returns the maximum number of concurrent borrowers of every object.
+    #[must_use]
+    pub fn max_borrowers(&self) -> usize {
+        self.inner.max_borrowers
+    }
+
+    /// Returns the number of leases of every object currently held by this
+    /// [`SharedPool`] by its [`ObjectId`].
+    #[must_use]
+    pub fn leases(&self) -> Vec<(ObjectId, usize)> {
+        self.inner
+            .entries
+            .lock()
+            .unwrap()
+            .iter()
+            .map(|entry| (Object::id(&entry.obj), entry.leases))
+            .collect()
+    }
+
+    /// Closes this [`SharedPool`] and the wrapped [`Pool`].
+    ///
+    /// All current and future tasks waiting for a lease will return
+    /// [`PoolError::Closed`] immediately.
+    pub fn close(&self) {
+        self.inner.semaphore.close();
+        self.inner.pool.close();
+    }
+
+    /// Indicates whether this [`SharedPool`] has been closed.
+    pub fn is_closed(&self) -> bool {
+        self.inner.semaphore.is_closed()
+    }
+}
+
+/// Lease of an object retrieved via [`SharedPool::get()`].
+///
+/// It derefs to the object. Clones share the same lease, i.e. the lease is
+/// released once the last clone is dropped.
+pub struct SharedObject<M: Manager> {
+    lease: Arc<Lease<M>>,
+}
+
+struct Lease<M: Manager> {
+    obj: Arc<Object<M>>,
+    pool: Weak<SharedPoolInner<M>>,
+}
+
+impl<M: Manager> Drop for Lease<M> {
+    fn drop(&mut self) {
+        let Some(pool) = self.pool.upgrade() else {
+            return;
+        };
+        let id = Object::id(&self.obj);
+        let removed = {
+            let mut entries = pool.entries.lock().unwrap();
+            let index = entries
+                .iter()
+                .position(|entry| Object::id(&entry.obj) == id);
+            index.and_then(|index| {
+                entries[index].leases -= 1;
+                (entries[index].leases == 0).then(|| entries.swap_remove(index))
+            })
+        };
+        // The object is returned to the pool once the last reference is
+        // dropped which happens after releasing the lock.
+        drop(removed);
+        pool.semaphore.add_permits(1);
+    }
+}
+
+impl<M: Manager> SharedObject<M> {
+    /// Returns the unique ID of the leased object.
+    pub fn id(this: &Self) -> ObjectId {
+        Object::id(&this.lease.obj)
+    }
+}
+
+impl<M: Manager> Clone for SharedObject<M> {
+    fn clone(&self) -> Self {
+        Self {
+            lease: self.lease.clone(),
+        }
+    }
+}
+
+impl<M: Manager> Deref for SharedObject<M> {
+    type Target = M::Type;
+
+    fn deref(&self) -> &M::Type {
+        &self.lease.obj
+    }
+}
+
+impl<M: Manager> AsRef<M::Type> for SharedObject<M> {
+    fn as_ref(&self) -> &M::Type {
+        self
+    }
+}
+
+impl<M> fmt::Debug for SharedObject<M>
+where
+    M: Manager,
+    M::Type: fmt::Debug,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("SharedObject")
+            .field("obj", &**self)
+            .finish()
+    }
+}
@@ -0,0 +1,117 @@
+//! Detection of nested checkouts from the same task.
+
+use std::{
+    cell::RefCell,
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll},
+};
+
+use super::dropguard::DropGuard;
+
+thread_local! {
+    /// Checkouts of the [`TrackCheckouts`] future currently being polled.
+    static CURRENT: RefCell<Option<Arc<Checkouts>>> = const { RefCell::new(None) };
+}
+
+/// Keys of the pools the objects currently held by a task were checked out
+/// from. A key is contained once per object.
+#[derive(Debug, Default)]
+struct Checkouts {
+    pools: Mutex<Vec<usize>>,
+}
+
+/// Wraps the given `future` so every object it checks out from a [`Pool`]
+/// is recorded.
+///
+/// If the `future` already holds an object of a [`Pool`] and tries to check
+/// out another one while the [`Pool`] is exhausted [`Pool::get()`] fails
+/// with [`PoolError::NestedCheckout`] instead of waiting. Such a checkout
+/// would deadlock once all objects are held by tasks waiting for a second
+/// one.
+///
+/// This is meant as a debugging aid, e.g. for wrapping request handlers in
+/// tests or debug builds. Checkouts outside of a [`TrackCheckouts`] future
+/// aren't recorded. Objects moved to other tasks remain recorded for the
+/// task which checked them out.
+///
+/// [`Pool`]: super::Pool
+/// [`Pool::get()`]: super::Pool::get
+/// [`PoolError::NestedCheckout`]: super::PoolError::NestedCheckout
+pub fn track_checkouts<F: Future>(future: F) -> TrackCheckouts<F> {
+    TrackCheckouts {
+        future: Box::pin(future),
+        checkouts: Arc::default(),
+    }
+}
+
+/// Future returned by [`track_checkouts()`].
+#[must_use = "futures do nothing unless you `.await` or poll them"]
+pub struct TrackCheckouts<F: Future> {
+    future: Pin<Box<F>>,
+    checkouts: Arc<Checkouts>,
+}
+
+impl<F: Future> Future for TrackCheckouts<F> {
+    type Output = F::Output;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<F::Output> {
+        let this = &mut *self;
+        let prev =
+            RefCell::new(CURRENT.with(|current| current.replace(Some(this.checkouts.clone()))));
+        // Restore the outer scope even if polling panics.
+        let _guard = DropGuard(|| CURRENT.with(|current| *current.borrow_mut() = prev.take()));
+        this.future.as_mut().poll(cx)
+    }
+}
+
+impl<F: Future> fmt::Debug for TrackCheckouts<F> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("TrackCheckouts")
+            .field("checkouts", &self.checkouts)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Checks whether the current task holds an object of the pool with the
+/// given key.
+pub(crate) fn holds(pool: usize) -> bool {
+    CURRENT.with(|current| {
+        current
+            .borrow()
+            .as_ref()
+            .is_some_and(|checkouts| checkouts.pools.lock().unwrap().contains(&pool))
+    })
+}
+
+/// Records a checkout from the pool with the given key if the current task
+/// is tracked.
+pub(crate) fn record(pool: usize) -> Option<CheckoutRecord> {
+    let checkouts = CURRENT.with(|current| current.borrow().clone())?;
+    checkouts.pools.lock().unwrap().push(pool);
+    Some(CheckoutRecord { checkouts, pool })
+}
+
+/// Record of a checked out object which is removed when the object is
+/// dropped.
+pub(crate) struct CheckoutRecord {
+    checkouts: Arc<Checkouts>,
+    pool: usize,
+}
+
+impl Drop for CheckoutRecord {
+    fn drop(&mut self) {
+        let mut pools = self.checkouts.pools.lock().unwrap();
+        if let Some(index) = pools.iter().position(|&pool| pool == self.pool) {
+            let _ = pools.swap_remove(index);
+        }
+    }
+}
+
+impl fmt::Debug for CheckoutRecord {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("CheckoutRecord").finish_non_exhaustive()
+    }
+}
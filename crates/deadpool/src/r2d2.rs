@@ -0,0 +1,118 @@
+//! Integration with the [`r2d2`](https://crates.io/crates/r2d2) connection
+//! pool.
+//!
+//! This module provides the [`PoolManager`] which implements
+//! [`ManageConnection`] on top of a [`Pool`]. This makes it possible to
+//! share a [`Pool`] with synchronous code written against the API of
+//! [`r2d2`].
+//!
+//! Every connection of the [`r2d2::Pool`] is an object
+//! checked out from the [`Pool`] via [`Pool::blocking_get()`]. Idle
+//! connections of the [`r2d2::Pool`] are therefore not
+//! available to other users of the [`Pool`]. Consider configuring the
+//! [`r2d2::Pool`] with a `min_idle` of `0` and a short
+//! `idle_timeout`.
+//!
+//! # Example
+//!
+//! ```rust
+//! use deadpool::{
+//!     managed::{self, Metrics, RecycleResult},
+//!     r2d2::PoolManager,
+//! };
+//!
+//! struct Manager {}
+//!
+//! impl managed::Manager for Manager {
+//!     type Type = usize;
+//!     type Error = std::convert::Infallible;
+//!
+//!     async fn create(&self) -> Result<usize, Self::Error> {
+//!         Ok(42)
+//!     }
+//!
+//!     async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Self::Error> {
+//!         Ok(())
+//!     }
+//! }
+//!
+//! type Pool = managed::Pool<Manager>;
+//!
+//! let pool = Pool::builder(Manager {}).max_size(4).build().unwrap();
+//! let r2d2_pool = r2d2::Pool::builder()
+//!     .max_size(2)
+//!     .build(PoolManager::new(pool))
+//!     .unwrap();
+//! let conn = r2d2_pool.get().unwrap();
+//! assert_eq!(**conn, 42);
+//! ```
+//!
+//! [`Pool`]: crate::managed::Pool
+//! [`Pool::blocking_get()`]: crate::managed::Pool::blocking_get
+
+use std::fmt;
+
+use ::r2d2::ManageConnection;
+
+use crate::managed::{Manager, Object, Pool, PoolError};
+
+/// [`ManageConnection`] implementation retrieving its connections from a
+/// [`Pool`].
+///
+/// Connections are retrieved via [`Pool::blocking_get()`] and returned to
+/// the [`Pool`] once the [`r2d2::Pool`] drops them.
+///
+/// [`Pool::blocking_get()`]: crate::managed::Pool::blocking_get
+pub struct PoolManager<M: Manager, W: From<Object<M>> = Object<M>> {
+    pool: Pool<M, W>,
+}
+
+impl<M: Manager, W: From<Object<M>>> PoolManager<M, W> {
+    /// Creates a new [`PoolManager`] using the given [`Pool`].
+    #[must_use]
+    pub fn new(pool: Pool<M, W>) -> Self {
+        Self { pool }
+    }
+
+    /// Returns the [`Pool`] used by this [`PoolManager`].
+    #[must_use]
+    pub fn pool(&self) -> &Pool<M, W> {
+        &self.pool
+    }
+}
+
+impl<M, W> ManageConnection for PoolManager<M, W>
+where
+    M: Manager + 'static,
+    M::Error: std::error::Error + 'static,
+    W: From<Object<M>> + Send + 'static,
+{
+    type Connection = W;
+    type Error = PoolError<M::Error>;
+
+    fn connect(&self) -> Result<W, Self::Error> {
+        self.pool.blocking_get()
+    }
+
+    /// Always succeeds as objects are already recycled by the [`Pool`]
+    /// when being checked out.
+    fn is_valid(&self, _: &mut W) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    fn has_broken(&self, _: &mut W) -> bool {
+        false
+    }
+}
+
+impl<M, W> fmt::Debug for PoolManager<M, W>
+where
+    M: Manager,
+    W: From<Object<M>>,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PoolManager")
+            .field("status", &self.pool.status())
+            .finish_non_exhaustive()
+    }
+}
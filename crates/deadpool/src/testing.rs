@@ -0,0 +1,255 @@
+//! Utilities for testing code which uses a [`managed::Pool`].
+//!
+//! This module provides a [`MockManager`] whose `create` and `recycle`
+//! outcomes can be scripted. This makes it possible to test retry and
+//! timeout handling without a real database.
+//!
+//! # Example
+//!
+//! ```rust
+//! use deadpool::{
+//!     managed::Pool,
+//!     testing::{self, MockError, MockManager},
+//! };
+//!
+//! #[tokio::main]
+//! async fn main() {
+//!     let mgr = MockManager::new();
+//!     mgr.push_create_result(Err(MockError::new("connection refused")));
+//!     let pool = Pool::<MockManager>::builder(mgr).max_size(2).build().unwrap();
+//!     assert!(pool.get().await.is_err());
+//!     drop(pool.get().await.unwrap());
+//!     assert_eq!(pool.manager().create_calls(), 2);
+//!     testing::assert_idle(&pool, 1);
+//!     testing::assert_no_leaks(&pool);
+//! }
+//! ```
+//!
+//! [`managed::Pool`]: crate::managed::Pool
+
+use std::{
+    borrow::Cow,
+    collections::VecDeque,
+    fmt,
+    future::pending,
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use deadpool_runtime::{Runtime, timeout};
+
+use crate::managed::{Manager, Metrics, Object, Pool, RecycleError, RecycleResult};
+
+/// [`Manager`] with scriptable outcomes for testing purposes.
+///
+/// Results pushed via [`MockManager::push_create_result()`] and
+/// [`MockManager::push_recycle_result()`] are consumed in order. Once a
+/// script is exhausted all further calls succeed.
+#[derive(Debug, Default)]
+pub struct MockManager {
+    runtime: Option<Runtime>,
+    create_results: Mutex<VecDeque<Result<(), MockError>>>,
+    recycle_results: Mutex<VecDeque<Result<(), MockError>>>,
+    create_latency: Mutex<Option<Duration>>,
+    recycle_latency: Mutex<Option<Duration>>,
+    next_id: AtomicUsize,
+    create_calls: AtomicUsize,
+    recycle_calls: AtomicUsize,
+    detach_calls: AtomicUsize,
+    live_objects: Arc<AtomicUsize>,
+}
+
+impl MockManager {
+    /// Creates a new [`MockManager`] where all calls succeed immediately.
+    #[must_use]
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Sets the [`Runtime`] which is used for injecting latency.
+    ///
+    /// This is required when using [`MockManager::set_create_latency()`] or
+    /// [`MockManager::set_recycle_latency()`].
+    #[must_use]
+    pub fn runtime(mut self, value: Runtime) -> Self {
+        self.runtime = Some(value);
+        self
+    }
+
+    /// Appends a result to the script of [`Manager::create()`] calls.
+    pub fn push_create_result(&self, result: Result<(), MockError>) {
+        self.create_results.lock().unwrap().push_back(result);
+    }
+
+    /// Appends a result to the script of [`Manager::recycle()`] calls.
+    pub fn push_recycle_result(&self, result: Result<(), MockError>) {
+        self.recycle_results.lock().unwrap().push_back(result);
+    }
+
+    /// Sets the latency of each [`Manager::create()`] call.
+    ///
+    /// # Panics
+    ///
+    /// Creating objects panics if no [`Runtime`] has been specified via
+    /// [`MockManager::runtime()`].
+    pub fn set_create_latency(&self, value: Option<Duration>) {
+        *self.create_latency.lock().unwrap() = value;
+    }
+
+    /// Sets the latency of each [`Manager::recycle()`] call.
+    ///
+    /// # Panics
+    ///
+    /// Recycling objects panics if no [`Runtime`] has been specified via
+    /// [`MockManager::runtime()`].
+    pub fn set_recycle_latency(&self, value: Option<Duration>) {
+        *self.recycle_latency.lock().unwrap() = value;
+    }
+
+    /// Returns the number of [`Manager::create()`] calls.
+    #[must_use]
+    pub fn create_calls(&self) -> usize {
+        self.create_calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of [`Manager::recycle()`] calls.
+    #[must_use]
+    pub fn recycle_calls(&self) -> usize {
+        self.recycle_calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of [`Manager::detach()`] calls.
+    #[must_use]
+    pub fn detach_calls(&self) -> usize {
+        self.detach_calls.load(Ordering::Relaxed)
+    }
+
+    /// Returns the number of [`MockObject`]s which haven't been dropped, yet.
+    #[must_use]
+    pub fn live_objects(&self) -> usize {
+        self.live_objects.load(Ordering::Relaxed)
+    }
+
+    async fn delay(&self, latency: Option<Duration>) {
+        if let Some(duration) = latency {
+            let runtime = self
+                .runtime
+                .expect("MockManager requires a runtime for injecting latency");
+            let _ = timeout(runtime, duration, pending::<()>()).await;
+        }
+    }
+}
+
+impl Manager for MockManager {
+    type Type = MockObject;
+    type Error = MockError;
+
+    async fn create(&self) -> Result<MockObject, MockError> {
+        let _ = self.create_calls.fetch_add(1, Ordering::Relaxed);
+        let latency = *self.create_latency.lock().unwrap();
+        self.delay(latency).await;
+        let result = self.create_results.lock().unwrap().pop_front();
+        result.unwrap_or(Ok(()))?;
+        let _ = self.live_objects.fetch_add(1, Ordering::Relaxed);
+        Ok(MockObject {
+            id: self.next_id.fetch_add(1, Ordering::Relaxed),
+            live_objects: self.live_objects.clone(),
+        })
+    }
+
+    async fn recycle(&self, _: &mut MockObject, _: &Metrics) -> RecycleResult<MockError> {
+        let _ = self.recycle_calls.fetch_add(1, Ordering::Relaxed);
+        let latency = *self.recycle_latency.lock().unwrap();
+        self.delay(latency).await;
+        let result = self.recycle_results.lock().unwrap().pop_front();
+        result.unwrap_or(Ok(())).map_err(RecycleError::Backend)
+    }
+
+    fn detach(&self, _: &mut MockObject) {
+        let _ = self.detach_calls.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+/// Object created by the [`MockManager`].
+#[derive(Debug)]
+pub struct MockObject {
+    id: usize,
+    live_objects: Arc<AtomicUsize>,
+}
+
+impl MockObject {
+    /// Returns the ID of this object. IDs are assigned by the
+    /// [`MockManager`] in increasing order starting at `0`.
+    #[must_use]
+    pub fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl Drop for MockObject {
+    fn drop(&mut self) {
+        let _ = self.live_objects.fetch_sub(1, Ordering::Relaxed);
+    }
+}
+
+/// Error returned by the [`MockManager`].
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct MockError(Cow<'static, str>);
+
+impl MockError {
+    /// Creates a new [`MockError`] with the given message.
+    pub fn new(msg: impl Into<Cow<'static, str>>) -> Self {
+        Self(msg.into())
+    }
+}
+
+impl fmt::Display for MockError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl std::error::Error for MockError {}
+
+/// Asserts that every [`MockObject`] which is still alive is accounted for
+/// by the [`Pool`].
+///
+/// Objects which have been removed from the [`Pool`] via [`Object::take()`]
+/// or [`Pool::retain()`] count as leaked as long as they are kept alive.
+///
+/// # Panics
+///
+/// Panics if the number of live objects differs from the size of the
+/// [`Pool`].
+#[track_caller]
+pub fn assert_no_leaks<W: From<Object<MockManager>>>(pool: &Pool<MockManager, W>) {
+    let live = pool.manager().live_objects();
+    let size = pool.status().size;
+    assert_eq!(
+        live, size,
+        "{} objects are alive but the pool only holds {}",
+        live, size
+    );
+}
+
+/// Asserts that the [`Pool`] holds exactly `n` objects which are all idle.
+///
+/// # Panics
+///
+/// Panics if the [`Pool`] doesn't hold `n` objects or if any of them is
+/// currently checked out.
+#[track_caller]
+pub fn assert_idle<W: From<Object<MockManager>>>(pool: &Pool<MockManager, W>, n: usize) {
+    let status = pool.status();
+    assert_eq!(
+        (status.size, status.available),
+        (n, n),
+        "expected {} idle objects, got size={} available={}",
+        n,
+        status.size,
+        status.available
+    );
+}
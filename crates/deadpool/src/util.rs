@@ -1,3 +1,11 @@
+#[cfg(feature = "managed")]
+use std::{
+    future::{Future, poll_fn},
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    thread::Thread,
+};
 use std::{num::NonZeroUsize, sync::LazyLock};
 
 /// Cache the logical CPU count to avoid calling
@@ -14,3 +22,67 @@ static CPU_COUNT: LazyLock<usize> = LazyLock::new(|| {
 pub(crate) fn get_default_pool_max_size() -> usize {
     *CPU_COUNT * 2
 }
+
+/// Polls all `futures` concurrently and returns their outputs in order.
+///
+/// If any of them fails the remaining ones are dropped and the error is
+/// returned.
+#[cfg(feature = "managed")]
+pub(crate) async fn try_join_all<T, E, F>(futures: impl IntoIterator<Item = F>) -> Result<Vec<T>, E>
+where
+    F: Future<Output = Result<T, E>>,
+{
+    let mut futures: Vec<_> = futures.into_iter().map(|f| Some(Box::pin(f))).collect();
+    let mut outputs: Vec<Option<T>> = futures.iter().map(|_| None).collect();
+    poll_fn(|cx| {
+        let mut pending = false;
+        for (future, output) in futures.iter_mut().zip(outputs.iter_mut()) {
+            let Some(f) = future else {
+                continue;
+            };
+            match f.as_mut().poll(cx) {
+                Poll::Ready(Ok(value)) => {
+                    *output = Some(value);
+                    *future = None;
+                }
+                Poll::Ready(Err(e)) => return Poll::Ready(Err(e)),
+                Poll::Pending => pending = true,
+            }
+        }
+        if pending {
+            Poll::Pending
+        } else {
+            Poll::Ready(Ok(outputs.iter_mut().map(|o| o.take().unwrap()).collect()))
+        }
+    })
+    .await
+}
+
+/// Waker unparking the thread blocked in [`block_on()`].
+#[cfg(feature = "managed")]
+struct ThreadWaker(Thread);
+
+#[cfg(feature = "managed")]
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.0.unpark();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
+
+/// Runs the given `future` to completion by blocking the current thread.
+#[cfg(feature = "managed")]
+pub(crate) fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let waker = Waker::from(Arc::new(ThreadWaker(std::thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match future.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            Poll::Pending => std::thread::park(),
+        }
+    }
+}
@@ -0,0 +1,74 @@
+#![cfg(feature = "actix-web")]
+
+use std::convert::Infallible;
+
+use actix_web::{
+    App,
+    http::StatusCode,
+    test::{TestRequest, call_service, init_service},
+    web,
+};
+use deadpool::{
+    actix_web::Conn,
+    managed::{self, Metrics, RecycleResult},
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(42)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+async fn handler(obj: Conn<Pool>) -> String {
+    obj.to_string()
+}
+
+async fn request(pool: Option<Pool>) -> StatusCode {
+    let mut app = App::new().route("/", web::get().to(handler));
+    if let Some(pool) = pool {
+        app = app.app_data(web::Data::new(pool));
+    }
+    let app = init_service(app).await;
+    call_service(&app, TestRequest::get().uri("/").to_request())
+        .await
+        .status()
+}
+
+#[actix_rt::test]
+async fn extract() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    assert_eq!(request(Some(pool.clone())).await, StatusCode::OK);
+    assert_eq!(pool.status().available, 1);
+}
+
+#[actix_rt::test]
+async fn extract_plain_app_data() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let app = App::new().app_data(pool).route("/", web::get().to(handler));
+    let app = init_service(app).await;
+    let res = call_service(&app, TestRequest::get().uri("/").to_request()).await;
+    assert_eq!(res.status(), StatusCode::OK);
+}
+
+#[actix_rt::test]
+async fn closed() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    pool.close();
+    assert_eq!(request(Some(pool)).await, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[actix_rt::test]
+async fn missing_pool() {
+    assert_eq!(request(None).await, StatusCode::INTERNAL_SERVER_ERROR);
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "axum")]
+
+use std::time::Duration;
+
+use axum::{
+    Router,
+    body::Body,
+    http::{Request, StatusCode},
+    routing::get,
+};
+use deadpool::{
+    axum::{CheckoutLayer, Conn, StatusMapping},
+    managed::{self, Metrics, RecycleResult},
+};
+use tower::ServiceExt;
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(42)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+async fn handler(obj: Conn<Pool>) -> String {
+    obj.to_string()
+}
+
+fn app(pool: Pool, layer: CheckoutLayer) -> Router {
+    Router::new()
+        .route("/", get(handler))
+        .layer(layer)
+        .with_state(pool)
+}
+
+async fn request(app: Router) -> StatusCode {
+    let req = Request::new(Body::empty());
+    app.oneshot(req).await.unwrap().status()
+}
+
+#[tokio::test]
+async fn extract() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    assert_eq!(
+        request(app(pool.clone(), CheckoutLayer::new())).await,
+        StatusCode::OK
+    );
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn closed() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    pool.close();
+    let status = request(app(pool, CheckoutLayer::new())).await;
+    assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn deadline() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let _obj = pool.get().await.unwrap();
+    let layer = CheckoutLayer::new()
+        .deadline(Duration::from_millis(10))
+        .status_mapping(StatusMapping {
+            timeout: StatusCode::GATEWAY_TIMEOUT,
+            ..StatusMapping::default()
+        });
+    let status = request(app(pool, layer)).await;
+    assert_eq!(status, StatusCode::GATEWAY_TIMEOUT);
+}
@@ -91,15 +91,18 @@ async fn closing() {
     assert_eq!(pool.status().available, 0);
     assert_eq!(pool.status().waiting, 0);
 
-    assert!(matches!(join_handle.await.unwrap(), Err(PoolError::Closed)));
-    assert!(matches!(pool.get().await, Err(PoolError::Closed)));
+    assert!(matches!(
+        join_handle.await.unwrap(),
+        Err(PoolError::Closed(_))
+    ));
+    assert!(matches!(pool.get().await, Err(PoolError::Closed(_))));
     assert!(matches!(
         pool.timeout_get(&Timeouts {
             wait: Some(Duration::ZERO),
             ..pool.timeouts()
         })
         .await,
-        Err(PoolError::Closed)
+        Err(PoolError::Closed(_))
     ));
 
     drop(obj);
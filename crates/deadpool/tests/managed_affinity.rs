@@ -0,0 +1,120 @@
+#![cfg(feature = "managed")]
+
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use deadpool::managed::{self, Affinity, DiscardReason, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+fn pool(max_size: usize) -> Pool {
+    Pool::builder(Manager {
+        created: AtomicUsize::new(0),
+    })
+    .max_size(max_size)
+    .build()
+    .unwrap()
+}
+
+#[tokio::test]
+async fn affinity() {
+    let pool = pool(3);
+    {
+        let _a = pool.get_with_affinity("a").await.unwrap();
+        let _b = pool.get_with_affinity("b").await.unwrap();
+        let _c = pool.get().await.unwrap();
+    }
+    for _ in 0..3 {
+        let a = pool.get_with_affinity("a").await.unwrap();
+        assert_eq!(*a, 0);
+        assert_eq!(
+            Object::extensions(&a).get::<Affinity<&str>>(),
+            Some(&Affinity("a"))
+        );
+        assert_eq!(*pool.get_with_affinity("b").await.unwrap(), 1);
+    }
+    assert_eq!(pool.status().size, 3);
+}
+
+#[tokio::test]
+async fn affinity_fallback() {
+    let pool = pool(1);
+    drop(pool.get_with_affinity("a").await.unwrap());
+    let b = pool.get_with_affinity("b").await.unwrap();
+    assert_eq!(*b, 0);
+    assert_eq!(
+        Object::extensions(&b).get::<Affinity<&str>>(),
+        Some(&Affinity("b"))
+    );
+}
+
+#[tokio::test]
+async fn affinity_strict() {
+    let discarded = Arc::new(Mutex::new(Vec::new()));
+    let pool = Pool::builder(Manager {
+        created: AtomicUsize::new(0),
+    })
+    .max_size(2)
+    .on_discard({
+        let discarded = discarded.clone();
+        move |_, _, reason| discarded.lock().unwrap().push(reason)
+    })
+    .build()
+    .unwrap();
+    drop(pool.get_with_affinity_strict("a").await.unwrap());
+    // There is still room for a new object
+    assert_eq!(*pool.get_with_affinity_strict("b").await.unwrap(), 1);
+    assert_eq!(*pool.get_with_affinity_strict("a").await.unwrap(), 0);
+    assert!(discarded.lock().unwrap().is_empty());
+    // The pool is full so the least recently used object is evicted
+    assert_eq!(*pool.get_with_affinity_strict("c").await.unwrap(), 2);
+    assert_eq!(
+        discarded.lock().unwrap().as_slice(),
+        [DiscardReason::Evicted]
+    );
+    assert_eq!(pool.status().size, 2);
+}
+
+#[tokio::test]
+async fn get_where() {
+    let pool = pool(3);
+    {
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+        let _c = pool.get().await.unwrap();
+    }
+    let task = {
+        let pool = pool.clone();
+        tokio::spawn(async move {
+            *pool
+                .get_where(|id, _, _| id.to_string() == "1")
+                .await
+                .unwrap()
+        })
+    };
+    assert_eq!(task.await.unwrap(), 1);
+    let obj = pool
+        .get_where(|_, metrics, _| metrics.recycle_count > 0)
+        .await
+        .unwrap();
+    assert_eq!(*obj, 1);
+}
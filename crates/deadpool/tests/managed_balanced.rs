@@ -0,0 +1,122 @@
+#![cfg(feature = "managed")]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use deadpool::managed::{
+    self, BalanceStrategy, BalancedManager, Metrics, Object, PoolError, RecycleResult,
+};
+
+type Pool = managed::Pool<BalancedManager<Manager>>;
+
+struct Manager {
+    name: &'static str,
+    up: AtomicBool,
+}
+
+impl Manager {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            up: AtomicBool::new(true),
+        }
+    }
+}
+
+impl managed::Manager for Manager {
+    type Type = &'static str;
+    type Error = &'static str;
+
+    async fn create(&self) -> Result<&'static str, &'static str> {
+        if self.up.load(Ordering::SeqCst) {
+            Ok(self.name)
+        } else {
+            Err(self.name)
+        }
+    }
+
+    async fn recycle(&self, _: &mut &'static str, _: &Metrics) -> RecycleResult<&'static str> {
+        Ok(())
+    }
+}
+
+fn managers() -> [Manager; 3] {
+    [Manager::new("a"), Manager::new("b"), Manager::new("c")]
+}
+
+#[tokio::test]
+async fn round_robin() {
+    let pool = Pool::builder(BalancedManager::new(managers()))
+        .max_size(6)
+        .build()
+        .unwrap();
+    let mut objs = Vec::new();
+    for _ in 0..6 {
+        objs.push(pool.get().await.unwrap());
+    }
+    let names: Vec<_> = objs.iter().map(|obj| ***obj).collect();
+    assert_eq!(names, ["a", "b", "c", "a", "b", "c"]);
+    let mgr = pool.manager();
+    assert_eq!(mgr.objects(0), Some(2));
+    assert_eq!(mgr.objects(1), Some(2));
+    assert_eq!(mgr.objects(2), Some(2));
+}
+
+#[tokio::test]
+async fn least_objects() {
+    let mgr = BalancedManager::new(managers()).strategy(BalanceStrategy::LeastObjects);
+    let pool = Pool::builder(mgr).max_size(6).build().unwrap();
+    let a = pool.get().await.unwrap();
+    let b = pool.get().await.unwrap();
+    let c = pool.get().await.unwrap();
+    assert_eq!([a.index(), b.index(), c.index()], [0, 1, 2]);
+    let _ = Object::take(b);
+    assert_eq!(pool.manager().objects(1), Some(0));
+    assert_eq!(pool.get().await.unwrap().index(), 1);
+}
+
+#[tokio::test]
+async fn eject() {
+    let mgr = BalancedManager::new(managers());
+    mgr.manager(1).unwrap().up.store(false, Ordering::SeqCst);
+    let pool = Pool::builder(mgr).max_size(4).build().unwrap();
+    let mut objs = Vec::new();
+    for _ in 0..4 {
+        objs.push(pool.get().await.unwrap());
+    }
+    let names: Vec<_> = objs.iter().map(|obj| ***obj).collect();
+    assert_eq!(names, ["a", "c", "c", "a"]);
+    assert!(pool.manager().is_ejected(1));
+    assert_eq!(pool.manager().objects(1), Some(0));
+}
+
+#[tokio::test]
+async fn all_ejected() {
+    let mgr = BalancedManager::new(managers());
+    for i in 0..3 {
+        mgr.manager(i).unwrap().up.store(false, Ordering::SeqCst);
+    }
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    assert!(matches!(pool.get().await, Err(PoolError::Backend("c", _))));
+    // Ejected endpoints are still used if there are no others.
+    pool.manager()
+        .manager(2)
+        .unwrap()
+        .up
+        .store(true, Ordering::SeqCst);
+    assert_eq!(pool.get().await.unwrap().index(), 2);
+    assert!(!pool.manager().is_ejected(2));
+}
+
+#[tokio::test]
+async fn objects_dropped() {
+    let pool = Pool::builder(BalancedManager::new(managers()))
+        .max_size(1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    assert_eq!(pool.manager().objects(0), Some(1));
+    drop(obj);
+    assert_eq!(pool.manager().objects(0), Some(1));
+    let _ = pool.retain(|_, _| false);
+    assert_eq!(pool.manager().objects(0), Some(0));
+}
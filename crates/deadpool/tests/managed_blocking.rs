@@ -0,0 +1,75 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{convert::Infallible, thread, time::Duration};
+
+use deadpool::{
+    Runtime,
+    managed::{self, Metrics, PoolError, RecycleResult, TimeoutType, Timeouts},
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(42)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[test]
+fn blocking_get() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.blocking_get().unwrap();
+    assert_eq!(*obj, 42);
+    let handle = thread::spawn({
+        let pool = pool.clone();
+        move || *pool.blocking_get().unwrap()
+    });
+    thread::sleep(Duration::from_millis(50));
+    // The thread is blocked until the object is returned.
+    assert_eq!(pool.status().waiting, 1);
+    drop(obj);
+    assert_eq!(handle.join().unwrap(), 42);
+    assert_eq!(pool.status().available, 1);
+}
+
+#[test]
+fn blocking_timeout_get() {
+    let rt = tokio::runtime::Runtime::new().unwrap();
+    let _guard = rt.enter();
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let _obj = pool.blocking_get().unwrap();
+    let timeouts = Timeouts {
+        wait: Some(Duration::from_millis(10)),
+        ..Timeouts::default()
+    };
+    assert!(matches!(
+        pool.blocking_timeout_get(&timeouts),
+        Err(PoolError::Timeout(TimeoutType::Wait, _))
+    ));
+}
+
+#[tokio::test]
+async fn shared_with_async() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    let handle = tokio::task::spawn_blocking({
+        let pool = pool.clone();
+        move || drop(pool.blocking_get().unwrap())
+    });
+    drop(obj);
+    handle.await.unwrap();
+    assert_eq!(pool.status().size, 1);
+}
@@ -0,0 +1,157 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    convert::Infallible,
+    sync::{Arc, Mutex},
+    time::Duration,
+};
+
+use deadpool::{
+    Runtime,
+    managed::{
+        self, Budget, DiscardReason, Metrics, Object, PoolError, RecycleResult, TimeoutType,
+    },
+};
+
+struct NumberManager {}
+
+impl managed::Manager for NumberManager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(42)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+struct StringManager {}
+
+impl managed::Manager for StringManager {
+    type Type = String;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<String, Infallible> {
+        Ok("42".to_owned())
+    }
+
+    async fn recycle(&self, _: &mut String, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+type Discarded = Arc<Mutex<Vec<DiscardReason>>>;
+
+fn pools(
+    budget: &Budget,
+) -> (
+    managed::Pool<NumberManager>,
+    managed::Pool<StringManager>,
+    Discarded,
+) {
+    let discarded = Discarded::default();
+    let numbers = managed::Pool::builder(NumberManager {})
+        .max_size(4)
+        .budget(budget.clone())
+        .on_discard({
+            let discarded = discarded.clone();
+            move |_, _, reason| discarded.lock().unwrap().push(reason)
+        })
+        .build()
+        .unwrap();
+    let strings = managed::Pool::builder(StringManager {})
+        .max_size(4)
+        .budget(budget.clone())
+        .wait_timeout(Some(Duration::from_millis(50)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    (numbers, strings, discarded)
+}
+
+#[tokio::test]
+async fn limit() {
+    let budget = Budget::new(2);
+    let (numbers, strings, _) = pools(&budget);
+    let _a = numbers.get().await.unwrap();
+    let _b = numbers.get().await.unwrap();
+    assert_eq!(budget.live(), 2);
+    assert!(matches!(
+        strings.get().await,
+        Err(PoolError::Timeout(TimeoutType::Budget, _))
+    ));
+    assert_eq!(strings.status().size, 0);
+}
+
+#[tokio::test]
+async fn reclaim_idle() {
+    let budget = Budget::new(2);
+    let (numbers, strings, discarded) = pools(&budget);
+    drop(numbers.get().await.unwrap());
+    let _a = numbers.get().await.unwrap();
+    let b = numbers.get().await.unwrap();
+    drop(b);
+    assert_eq!(numbers.status().size, 2);
+    let _c = strings.get().await.unwrap();
+    assert_eq!(numbers.status().size, 1);
+    assert_eq!(*discarded.lock().unwrap(), [DiscardReason::Reclaimed]);
+    assert_eq!(budget.live(), 2);
+}
+
+#[tokio::test]
+async fn hand_over_returned() {
+    let budget = Budget::new(2);
+    let (numbers, strings, discarded) = pools(&budget);
+    let a = numbers.get().await.unwrap();
+    let _b = numbers.get().await.unwrap();
+    let waiter = tokio::spawn(async move { strings.get().await.map(|obj| obj.clone()) });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    drop(a);
+    assert_eq!(waiter.await.unwrap().unwrap(), "42");
+    assert_eq!(numbers.status().size, 1);
+    assert_eq!(*discarded.lock().unwrap(), [DiscardReason::Reclaimed]);
+}
+
+#[tokio::test]
+async fn reuse_returned_while_waiting() {
+    let budget = Budget::new(2);
+    let pool = managed::Pool::builder(NumberManager {})
+        .max_size(4)
+        .budget(budget.clone())
+        .wait_timeout(Some(Duration::from_secs(1)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let a = pool.get().await.unwrap();
+    let _b = pool.get().await.unwrap();
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.get().await.map(|obj| Object::id(&obj)) }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    let id = Object::id(&a);
+    drop(a);
+    let result = tokio::time::timeout(Duration::from_millis(100), waiter).await;
+    // The returned object is handed over instead of waiting for the budget.
+    assert_eq!(result.unwrap().unwrap().unwrap(), id);
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(budget.live(), 2);
+}
+
+#[tokio::test]
+async fn released() {
+    let budget = Budget::new(2);
+    let (numbers, _, _) = pools(&budget);
+    let a = numbers.get().await.unwrap();
+    let b = numbers.get().await.unwrap();
+    assert_eq!(budget.live(), 2);
+    // Taken objects are no longer tracked and leave the budget.
+    let _ = Object::take(a);
+    assert_eq!(budget.live(), 1);
+    drop(numbers);
+    drop(b);
+    assert_eq!(budget.live(), 0);
+}
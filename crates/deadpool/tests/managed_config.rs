@@ -53,6 +53,10 @@ fn from_env() {
     env.set("POOL__TIMEOUTS__CREATE__NANOS", "0");
     env.set("POOL__TIMEOUTS__RECYCLE__SECS", "3");
     env.set("POOL__TIMEOUTS__RECYCLE__NANOS", "0");
+    env.set("POOL__MAX_USES", "100");
+    env.set("POOL__RECYCLE_CHECK_INTERVAL__SECS", "4");
+    env.set("POOL__RECYCLE_CHECK_INTERVAL__NANOS", "0");
+    env.set("POOL__MAX_WAITING", "10");
 
     let cfg = Config::builder()
         .add_source(config::Environment::default().separator("__"))
@@ -65,4 +69,10 @@ fn from_env() {
     assert_eq!(cfg.pool.timeouts.wait, Some(Duration::from_secs(1)));
     assert_eq!(cfg.pool.timeouts.create, Some(Duration::from_secs(2)));
     assert_eq!(cfg.pool.timeouts.recycle, Some(Duration::from_secs(3)));
+    assert_eq!(cfg.pool.max_uses, Some(100));
+    assert_eq!(
+        cfg.pool.recycle_check_interval,
+        Some(Duration::from_secs(4))
+    );
+    assert_eq!(cfg.pool.max_waiting, Some(10));
 }
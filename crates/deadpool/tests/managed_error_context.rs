@@ -0,0 +1,75 @@
+#![cfg(feature = "managed")]
+
+use std::sync::atomic::{AtomicBool, Ordering};
+
+use deadpool::managed::{self, DiscardReason, Metrics, PoolError, RecycleError, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug, Default)]
+struct Manager {
+    broken: AtomicBool,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = &'static str;
+
+    async fn create(&self) -> Result<(), &'static str> {
+        if self.broken.load(Ordering::Relaxed) {
+            Err("connection refused")
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<&'static str> {
+        Err(RecycleError::Backend("connection reset"))
+    }
+}
+
+#[tokio::test]
+async fn context() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .build()
+        .unwrap();
+    {
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+    }
+    pool.manager().broken.store(true, Ordering::Relaxed);
+    let err = pool.get().await.unwrap_err();
+    assert!(matches!(err, PoolError::Backend("connection refused", _)));
+    let ctx = err.context();
+    assert!(!ctx.is_empty());
+    assert!(ctx.elapsed().is_some());
+    assert_eq!(ctx.status().unwrap().size, 0);
+    assert_eq!(ctx.discarded(), 2);
+    assert_eq!(
+        ctx.last_discard_reason(),
+        Some(DiscardReason::RecycleFailed)
+    );
+    assert!(matches!(
+        ctx.last_recycle_error(),
+        Some(RecycleError::Backend("connection reset"))
+    ));
+    let msg = err.to_string();
+    assert!(msg.starts_with("Error occurred while creating a new object: connection refused ("));
+    assert!(msg.contains("size: 0/2"));
+    assert!(msg.contains("discarded: 2"));
+    assert!(msg.contains(
+        "last recycle error: Error occurred while recycling an object: connection reset"
+    ));
+}
+
+#[test]
+fn empty_context() {
+    let err = PoolError::from("connection refused");
+    assert!(err.context().is_empty());
+    assert_eq!(err.context().discarded(), 0);
+    assert_eq!(
+        err.to_string(),
+        "Error occurred while creating a new object: connection refused"
+    );
+}
@@ -0,0 +1,86 @@
+#![cfg(feature = "managed")]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{self, Extensions, Hook, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug, PartialEq)]
+struct Tenant(&'static str);
+
+struct RecycleCount(usize);
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        unreachable!()
+    }
+
+    async fn recycle_with_extensions(
+        &self,
+        _: &mut (),
+        _: &Metrics,
+        extensions: &mut Extensions,
+    ) -> RecycleResult<Infallible> {
+        extensions.get_or_insert_with(|| RecycleCount(0)).0 += 1;
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn survive_recycling() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    {
+        let mut obj = pool.get().await.unwrap();
+        assert!(Object::extensions(&obj).is_empty());
+        assert_eq!(Object::extensions_mut(&mut obj).insert(Tenant("a")), None);
+    }
+    {
+        let mut obj = pool.get().await.unwrap();
+        let extensions = Object::extensions_mut(&mut obj);
+        assert_eq!(extensions.get::<Tenant>(), Some(&Tenant("a")));
+        assert_eq!(extensions.get::<RecycleCount>().unwrap().0, 1);
+        assert_eq!(extensions.insert(Tenant("b")), Some(Tenant("a")));
+    }
+    let obj = pool.get().await.unwrap();
+    let extensions = Object::extensions(&obj);
+    assert_eq!(extensions.get::<Tenant>(), Some(&Tenant("b")));
+    assert_eq!(extensions.get::<RecycleCount>().unwrap().0, 2);
+    assert_eq!(extensions.len(), 2);
+}
+
+#[tokio::test]
+async fn hooks() {
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .post_create(Hook::sync_fn_with_extensions(|_, _, extensions| {
+            let _ = extensions.insert(Tenant("default"));
+            Ok(())
+        }))
+        .post_recycle(Hook::async_fn_with_extensions(|_, _, extensions| {
+            Box::pin(async move {
+                assert!(extensions.remove::<Tenant>().is_some());
+                Ok(())
+            })
+        }))
+        .build()
+        .unwrap();
+    {
+        let obj = pool.get().await.unwrap();
+        assert_eq!(
+            Object::extensions(&obj).get::<Tenant>(),
+            Some(&Tenant("default"))
+        );
+    }
+    let obj = pool.get().await.unwrap();
+    assert!(!Object::extensions(&obj).contains::<Tenant>());
+}
@@ -0,0 +1,174 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    future::pending,
+    sync::atomic::{AtomicBool, AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use deadpool::{
+    Runtime,
+    managed::{self, FailoverError, FailoverManager, Metrics, Object, PoolError, RecycleResult},
+};
+
+type Pool = managed::Pool<FailoverManager<Manager>>;
+
+struct Manager {
+    name: &'static str,
+    up: AtomicBool,
+    hang: bool,
+    detached: AtomicUsize,
+}
+
+impl Manager {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            up: AtomicBool::new(true),
+            hang: false,
+            detached: AtomicUsize::new(0),
+        }
+    }
+
+    fn down(self) -> Self {
+        self.up.store(false, Ordering::SeqCst);
+        self
+    }
+}
+
+impl managed::Manager for Manager {
+    type Type = &'static str;
+    type Error = &'static str;
+
+    async fn create(&self) -> Result<&'static str, &'static str> {
+        if self.hang {
+            pending::<()>().await;
+        }
+        if self.up.load(Ordering::SeqCst) {
+            Ok(self.name)
+        } else {
+            Err(self.name)
+        }
+    }
+
+    async fn recycle(&self, _: &mut &'static str, _: &Metrics) -> RecycleResult<&'static str> {
+        Ok(())
+    }
+
+    fn detach(&self, _: &mut &'static str) {
+        let _ = self.detached.fetch_add(1, Ordering::SeqCst);
+    }
+}
+
+#[tokio::test]
+async fn primary() {
+    let mgr = FailoverManager::new(Manager::new("primary"), [Manager::new("secondary")]);
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    assert_eq!(obj.index(), 0);
+    assert_eq!(**obj, "primary");
+}
+
+#[tokio::test]
+async fn failover_and_back() {
+    let mgr = FailoverManager::new(Manager::new("primary").down(), [Manager::new("secondary")])
+        .create_timeout(Runtime::Tokio1, Duration::from_secs(1))
+        .retry_interval(Duration::ZERO);
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    {
+        let obj = pool.get().await.unwrap();
+        assert_eq!(obj.index(), 1);
+        assert_eq!(**obj, "secondary");
+        assert!(!pool.manager().is_healthy(0));
+    }
+    // The secondary object is retired in order to probe the primary which
+    // is still down.
+    assert_eq!(pool.get().await.unwrap().index(), 1);
+    pool.manager()
+        .manager(0)
+        .unwrap()
+        .up
+        .store(true, Ordering::SeqCst);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(obj.index(), 0);
+    assert!(pool.manager().is_healthy(0));
+    let mgr = pool.manager();
+    // Probing never creates an object which is thrown away.
+    assert_eq!(mgr.manager(0).unwrap().detached.load(Ordering::SeqCst), 0);
+    assert_eq!(mgr.manager(1).unwrap().detached.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn no_probe_without_create_timeout() {
+    let mgr = FailoverManager::new(Manager::new("primary").down(), [Manager::new("secondary")])
+        .retry_interval(Duration::ZERO);
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let _ = pool.get().await.unwrap();
+    pool.manager()
+        .manager(0)
+        .unwrap()
+        .up
+        .store(true, Ordering::SeqCst);
+    // Probing could delay the checkout indefinitely so the secondary object
+    // is kept.
+    assert_eq!(pool.get().await.unwrap().index(), 1);
+    assert!(!pool.manager().is_healthy(0));
+    let mgr = pool.manager();
+    assert_eq!(mgr.manager(1).unwrap().detached.load(Ordering::SeqCst), 0);
+}
+
+#[tokio::test]
+async fn retry_interval() {
+    let mgr = FailoverManager::new(Manager::new("primary").down(), [Manager::new("secondary")]);
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let _ = pool.get().await.unwrap();
+    pool.manager()
+        .manager(0)
+        .unwrap()
+        .up
+        .store(true, Ordering::SeqCst);
+    // The primary is not probed before the retry interval has passed.
+    assert_eq!(pool.get().await.unwrap().index(), 1);
+    assert!(!pool.manager().is_healthy(0));
+}
+
+#[tokio::test]
+async fn create_timeout() {
+    let mut primary = Manager::new("primary");
+    primary.hang = true;
+    let mgr = FailoverManager::new(primary, [Manager::new("secondary")])
+        .create_timeout(Runtime::Tokio1, Duration::from_millis(10));
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    assert_eq!(pool.get().await.unwrap().index(), 1);
+    assert!(!pool.manager().is_healthy(0));
+}
+
+#[tokio::test]
+async fn all_failed() {
+    let mgr = FailoverManager::new(
+        Manager::new("primary").down(),
+        [Manager::new("secondary").down()],
+    );
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Backend(FailoverError::Backend("secondary"), _))
+    ));
+    // All managers failed recently so all of them are tried again.
+    pool.manager()
+        .manager(0)
+        .unwrap()
+        .up
+        .store(true, Ordering::SeqCst);
+    assert_eq!(pool.get().await.unwrap().index(), 0);
+}
+
+#[tokio::test]
+async fn detach() {
+    let mgr = FailoverManager::new(Manager::new("primary").down(), [Manager::new("secondary")]);
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    let _ = Object::take(pool.get().await.unwrap());
+    let mgr = pool.manager();
+    assert_eq!(mgr.manager(0).unwrap().detached.load(Ordering::SeqCst), 0);
+    assert_eq!(mgr.manager(1).unwrap().detached.load(Ordering::SeqCst), 1);
+}
@@ -1,8 +1,13 @@
 #![cfg(feature = "managed")]
 
-use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
 
-use deadpool::managed::{Hook, HookError, Manager, Metrics, Pool, RecycleResult};
+use deadpool::managed::{
+    DiscardReason, Hook, HookError, Manager, Metrics, Object, Pool, RecycleError, RecycleResult,
+};
 
 struct Computer {
     next_id: AtomicUsize,
@@ -182,3 +187,52 @@ async fn post_recycle_err_continue() {
     assert_eq!(pool.status().available, 1);
     assert_eq!(pool.status().size, 1);
 }
+
+#[tokio::test]
+async fn pre_return_ok() {
+    let manager = Computer::new(42);
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .pre_return(Hook::async_fn(|obj, _| {
+            Box::pin(async move {
+                *obj += 100;
+                Ok(())
+            })
+        }))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    Object::release(obj).await.unwrap();
+    assert_eq!(pool.status().available, 1);
+    assert_eq!(pool.status().size, 1);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(*obj, 142);
+    // Dropping an object doesn't call the hook.
+    drop(obj);
+    assert_eq!(*pool.get().await.unwrap(), 142);
+}
+
+#[tokio::test]
+async fn pre_return_err_discard() {
+    let manager = Computer::new(0);
+    let discarded = Arc::new(Mutex::new(Vec::new()));
+    let pool = Pool::<Computer>::builder(manager)
+        .max_size(1)
+        .pre_return(Hook::sync_fn(|_, _| Err(HookError::message("Fail!"))))
+        .on_discard({
+            let discarded = discarded.clone();
+            move |_, _, reason| discarded.lock().unwrap().push(reason)
+        })
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    let err = Object::release(obj).await.unwrap_err();
+    assert!(matches!(err, RecycleError::Message(msg) if msg == "Fail!"));
+    assert_eq!(
+        *discarded.lock().unwrap(),
+        [DiscardReason::PreReturnHookFailed]
+    );
+    assert_eq!(pool.status().available, 0);
+    assert_eq!(pool.status().size, 0);
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
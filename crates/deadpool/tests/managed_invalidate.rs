@@ -0,0 +1,149 @@
+#![cfg(feature = "managed")]
+
+use std::sync::{
+    Arc,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use deadpool::managed::{self, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {
+    created: AtomicUsize,
+    detached: AtomicUsize,
+}
+
+impl Manager {
+    fn new() -> Self {
+        Self {
+            created: AtomicUsize::new(0),
+            detached: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+
+    fn detach(&self, _: &mut usize) {
+        self.detached.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[tokio::test]
+async fn invalidate_idle() {
+    let pool = Pool::builder(Manager::new()).max_size(2).build().unwrap();
+    {
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+    }
+    assert_eq!(pool.status().size, 2);
+    pool.invalidate();
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.available, 0);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 2);
+    assert_eq!(*pool.get().await.unwrap(), 2);
+}
+
+#[tokio::test]
+async fn invalidate_checked_out() {
+    let pool = Pool::builder(Manager::new()).max_size(2).build().unwrap();
+    let a = pool.get().await.unwrap();
+    pool.invalidate();
+    let b = pool.get().await.unwrap();
+    assert_eq!(pool.status().size, 2);
+    drop(a);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 0);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 1);
+    drop(b);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+    // The object created after the invalidation is reused
+    assert_eq!(*pool.get().await.unwrap(), 1);
+}
+
+#[tokio::test]
+async fn invalidate_where() {
+    let pool = Pool::builder(Manager::new()).max_size(3).build().unwrap();
+    let a = pool.get().await.unwrap();
+    let b = pool.get().await.unwrap();
+    let c = pool.get().await.unwrap();
+    let id_b = Object::id(&b);
+    drop(c);
+    pool.invalidate_where(move |id, _| id <= id_b);
+    // The idle object doesn't match the predicate
+    assert_eq!(pool.status().size, 3);
+    drop(a);
+    drop(b);
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 2);
+    // Objects created after the invalidation are not affected
+    let objs = [
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    assert_eq!(objs.iter().map(|o| **o).collect::<Vec<_>>(), [2, 3, 4]);
+    drop(objs);
+    assert_eq!(pool.status().size, 3);
+}
+
+#[tokio::test]
+async fn invalidate_with_waiter() {
+    let pool = Pool::builder(Manager::new()).max_size(1).build().unwrap();
+    let a = pool.get().await.unwrap();
+    let id_a = Object::id(&a);
+    let pool_clone = pool.clone();
+    let task = tokio::spawn(async move { *pool_clone.get().await.unwrap() });
+    tokio::task::yield_now().await;
+    assert_eq!(pool.status().waiting, 1);
+    pool.invalidate_where(move |id, _| id == id_a);
+    drop(a);
+    assert_eq!(task.await.unwrap(), 1);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 1);
+}
+
+#[tokio::test]
+async fn invalidate_where_prunes_predicates() {
+    let pool = Pool::builder(Manager::new()).max_size(2).build().unwrap();
+    let token = Arc::new(());
+    let a = pool.get().await.unwrap();
+    for _ in 0..3 {
+        let token = token.clone();
+        pool.invalidate_where(move |_, _| {
+            let _ = &token;
+            false
+        });
+    }
+    // The checked out object might still match the predicates
+    assert_eq!(Arc::strong_count(&token), 4);
+    drop(a);
+    // The object didn't match and is still alive
+    assert_eq!(Arc::strong_count(&token), 4);
+    let _ = pool.retain(|_, _| false);
+    assert_eq!(Arc::strong_count(&token), 1);
+    // Predicates are dropped right away if there are no older objects
+    let token_clone = token.clone();
+    pool.invalidate_where(move |_, _| {
+        let _ = &token_clone;
+        false
+    });
+    assert_eq!(Arc::strong_count(&token), 1);
+}
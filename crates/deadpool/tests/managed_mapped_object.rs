@@ -0,0 +1,110 @@
+#![cfg(feature = "managed")]
+
+use std::convert::Infallible;
+
+use deadpool::managed::{self, MappedObject, Metrics, Object, RecycleResult};
+
+#[derive(Debug)]
+struct Conn {
+    id: usize,
+    cache: Vec<&'static str>,
+    log: Vec<&'static str>,
+}
+
+#[derive(Debug, Default)]
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = Conn;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<Conn, Infallible> {
+        Ok(Conn {
+            id: 42,
+            cache: Vec::new(),
+            log: Vec::new(),
+        })
+    }
+
+    async fn recycle(&self, _: &mut Conn, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn map() {
+    let pool = managed::Pool::<Manager>::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    let id = Object::id(&obj);
+    let mut cache = Object::map(obj, |conn| &conn.cache, |conn| &mut conn.cache);
+    cache.push("foo");
+    assert_eq!(*cache, ["foo"]);
+    assert_eq!(Object::id(MappedObject::object(&cache)), id);
+    assert_eq!(pool.status().available, 0);
+
+    let index = 0;
+    let mut first = MappedObject::map(
+        cache,
+        move |cache| &cache[index],
+        move |cache| &mut cache[index],
+    );
+    assert_eq!(*first, "foo");
+    *first = "bar";
+    assert_eq!(MappedObject::object(&first).cache, ["bar"]);
+    drop(first);
+
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+    let obj = pool.get().await.unwrap();
+    assert_eq!(Object::id(&obj), id);
+    assert_eq!(obj.cache, ["bar"]);
+}
+
+#[tokio::test]
+async fn into_object() {
+    let pool = managed::Pool::<Manager>::builder(Manager::default())
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    let cache = Object::map(obj, |conn| &conn.cache, |conn| &mut conn.cache);
+    let obj = MappedObject::into_object(cache);
+    assert_eq!(obj.id, 42);
+    assert_eq!(pool.status().available, 0);
+}
+
+#[cfg(debug_assertions)]
+#[tokio::test]
+#[should_panic(expected = "must return the same component")]
+async fn map_different_components() {
+    let pool = managed::Pool::<Manager>::builder(Manager::default())
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    let _ = Object::map(obj, |conn| &conn.cache, |conn| &mut conn.log);
+}
+
+struct Cache(MappedObject<Manager, Vec<&'static str>>);
+
+impl From<Object<Manager>> for Cache {
+    fn from(obj: Object<Manager>) -> Self {
+        Self(Object::map(obj, |conn| &conn.cache, |conn| &mut conn.cache))
+    }
+}
+
+#[tokio::test]
+async fn wrapper() {
+    let pool = managed::Pool::<Manager, Cache>::builder(Manager::default())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let mut cache = pool.get().await.unwrap();
+    cache.0.push("foo");
+    drop(cache);
+    assert_eq!(pool.status().available, 1);
+    let cache = pool.get().await.unwrap();
+    assert_eq!(*cache.0, ["foo"]);
+}
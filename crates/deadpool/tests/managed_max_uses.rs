@@ -0,0 +1,107 @@
+#![cfg(feature = "managed")]
+
+use std::sync::{
+    Arc, Mutex,
+    atomic::{AtomicUsize, Ordering},
+};
+
+use deadpool::managed::{
+    self, BuildError, DiscardReason, Metrics, Object, ObjectId, RecycleResult,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+type Discarded = Arc<Mutex<Vec<(ObjectId, DiscardReason)>>>;
+
+fn pool(max_uses: Option<usize>) -> (Pool, Discarded) {
+    let discarded = Discarded::default();
+    let pool = Pool::builder(Manager {
+        created: AtomicUsize::new(0),
+    })
+    .max_size(1)
+    .max_uses(max_uses)
+    .on_discard({
+        let discarded = discarded.clone();
+        move |id, _, reason| discarded.lock().unwrap().push((id, reason))
+    })
+    .build()
+    .unwrap();
+    (pool, discarded)
+}
+
+#[tokio::test]
+async fn max_uses() {
+    let (pool, discarded) = pool(Some(3));
+    let mut ids = Vec::new();
+    for _ in 0..7 {
+        ids.push(*pool.get().await.unwrap());
+    }
+    assert_eq!(ids, [0, 0, 0, 1, 1, 1, 2]);
+    let discarded = discarded.lock().unwrap();
+    assert_eq!(discarded.len(), 2);
+    assert!(
+        discarded
+            .iter()
+            .all(|(_, reason)| *reason == DiscardReason::MaxUsesReached)
+    );
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn max_uses_one() {
+    let (pool, discarded) = pool(Some(1));
+    for i in 0..3 {
+        assert_eq!(*pool.get().await.unwrap(), i);
+        assert_eq!(pool.status().size, 0);
+    }
+    assert_eq!(discarded.lock().unwrap().len(), 3);
+}
+
+#[tokio::test]
+async fn unlimited() {
+    let (pool, discarded) = pool(None);
+    for _ in 0..10 {
+        assert_eq!(*pool.get().await.unwrap(), 0);
+    }
+    assert!(discarded.lock().unwrap().is_empty());
+}
+
+#[tokio::test]
+async fn discard_reason_invalidated() {
+    let (pool, discarded) = pool(None);
+    let obj = pool.get().await.unwrap();
+    let id = Object::id(&obj);
+    pool.invalidate();
+    drop(obj);
+    assert_eq!(
+        discarded.lock().unwrap().as_slice(),
+        [(id, DiscardReason::Invalidated)]
+    );
+}
+
+#[test]
+fn max_uses_zero() {
+    let result = Pool::builder(Manager {
+        created: AtomicUsize::new(0),
+    })
+    .max_uses(Some(0))
+    .build();
+    assert!(matches!(result, Err(BuildError::InvalidMaxUses)));
+}
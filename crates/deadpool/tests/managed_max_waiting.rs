@@ -0,0 +1,62 @@
+#![cfg(feature = "managed")]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::managed::{self, Metrics, PoolError, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug)]
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn queue_full() {
+    let pool = Pool::builder(Manager {})
+        .max_size(1)
+        .max_waiting(Some(1))
+        .build()
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.get().await.map(drop) }
+    });
+    while pool.status().waiting == 0 {
+        tokio::time::sleep(Duration::from_millis(1)).await;
+    }
+    let err = pool.get().await.unwrap_err();
+    assert!(matches!(err, PoolError::QueueFull(_)));
+    assert_eq!(err.context().status().unwrap().waiting, 1);
+    assert_eq!(pool.status().waiting, 1);
+    drop(obj);
+    waiter.await.unwrap().unwrap();
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+async fn no_waiting() {
+    let pool = Pool::builder(Manager {})
+        .max_size(2)
+        .max_waiting(Some(0))
+        .build()
+        .unwrap();
+    // Objects which can be created or reused are handed out.
+    let _a = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    let _b = pool.get().await.unwrap();
+    assert!(matches!(pool.get().await, Err(PoolError::QueueFull(_))));
+    assert_eq!(pool.status().waiting, 0);
+}
@@ -0,0 +1,87 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::{
+    Runtime,
+    managed::{self, Metrics, PoolError, RecycleResult, TimeoutType, Timeouts, track_checkouts},
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug)]
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+fn pool(max_size: usize) -> Pool {
+    Pool::builder(Manager {})
+        .max_size(max_size)
+        .wait_timeout(Some(Duration::from_millis(10)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn nested_checkout() {
+    let pool = pool(2);
+    track_checkouts(async {
+        let obj1 = pool.get().await.unwrap();
+        // The pool isn't exhausted, yet.
+        let obj2 = pool.get().await.unwrap();
+        let err = pool.get().await.unwrap_err();
+        assert!(matches!(err, PoolError::NestedCheckout(_)));
+        drop(obj1);
+        let _obj3 = pool.get().await.unwrap();
+        drop(obj2);
+    })
+    .await;
+    assert_eq!(pool.status().available, 2);
+}
+
+#[tokio::test]
+async fn untracked() {
+    let pool = pool(1);
+    let _obj = pool.get().await.unwrap();
+    let err = pool.get().await.unwrap_err();
+    assert!(matches!(err, PoolError::Timeout(TimeoutType::Wait, _)));
+}
+
+#[tokio::test]
+async fn other_task() {
+    let pool = pool(1);
+    let _obj = track_checkouts(pool.get()).await.unwrap();
+    // The object was checked out by another tracked future.
+    let err = track_checkouts(pool.get()).await.unwrap_err();
+    assert!(matches!(err, PoolError::Timeout(TimeoutType::Wait, _)));
+}
+
+#[tokio::test]
+async fn other_pool() {
+    let pool1 = pool(1);
+    let pool2 = pool(1);
+    track_checkouts(async {
+        let _obj = pool1.get().await.unwrap();
+        let _obj = pool2.get().await.unwrap();
+        let err = pool2.get().await.unwrap_err();
+        assert!(matches!(err, PoolError::NestedCheckout(_)));
+        let err = pool1
+            .timeout_get(&Timeouts::wait_millis(10))
+            .await
+            .unwrap_err();
+        assert!(matches!(err, PoolError::NestedCheckout(_)));
+    })
+    .await;
+}
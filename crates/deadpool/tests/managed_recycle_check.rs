@@ -0,0 +1,103 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    convert::Infallible,
+    sync::{
+        Arc,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use deadpool::managed::{self, Hook, Metrics, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    recycled: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        let _ = self.recycled.fetch_add(1, Ordering::SeqCst);
+        Ok(())
+    }
+}
+
+fn pool(interval: Option<Duration>) -> Pool {
+    Pool::builder(Manager::default())
+        .max_size(1)
+        .recycle_check_interval(interval)
+        .build()
+        .unwrap()
+}
+
+fn recycled(pool: &Pool) -> usize {
+    pool.manager().recycled.load(Ordering::SeqCst)
+}
+
+#[tokio::test]
+async fn disabled() {
+    let pool = pool(None);
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(recycled(&pool), 2);
+}
+
+#[tokio::test]
+async fn skipped_within_interval() {
+    let post_recycle = Arc::new(AtomicUsize::new(0));
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .recycle_check_interval(Some(Duration::from_secs(60)))
+        .post_recycle(Hook::sync_fn({
+            let post_recycle = post_recycle.clone();
+            move |_, _| {
+                let _ = post_recycle.fetch_add(1, Ordering::SeqCst);
+                Ok(())
+            }
+        }))
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    let obj = pool.get().await.unwrap();
+    assert_eq!(recycled(&pool), 0);
+    // Hooks are still run.
+    assert_eq!(post_recycle.load(Ordering::SeqCst), 2);
+    assert_eq!(managed::Object::metrics(&obj).recycle_count, 2);
+}
+
+#[tokio::test]
+async fn checked_after_interval() {
+    let pool = pool(Some(Duration::from_millis(50)));
+    drop(pool.get().await.unwrap());
+    drop(pool.get().await.unwrap());
+    assert_eq!(recycled(&pool), 0);
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    drop(pool.get().await.unwrap());
+    assert_eq!(recycled(&pool), 1);
+    // Recycling validated the object again.
+    drop(pool.get().await.unwrap());
+    assert_eq!(recycled(&pool), 1);
+}
+
+#[tokio::test]
+async fn run_validates() {
+    let pool = pool(Some(Duration::from_millis(100)));
+    drop(pool.get().await.unwrap());
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    pool.run(async |_| Ok(())).await.unwrap();
+    tokio::time::sleep(Duration::from_millis(60)).await;
+    drop(pool.get().await.unwrap());
+    assert_eq!(recycled(&pool), 0);
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "managed")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use deadpool::managed::{self, Metrics, RecycleResult, RetirePolicy};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {
+    name: &'static str,
+    recycled: AtomicUsize,
+    detached: AtomicUsize,
+}
+
+impl Manager {
+    fn new(name: &'static str) -> Self {
+        Self {
+            name,
+            recycled: AtomicUsize::new(0),
+            detached: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl managed::Manager for Manager {
+    type Type = &'static str;
+    type Error = ();
+
+    async fn create(&self) -> Result<&'static str, ()> {
+        Ok(self.name)
+    }
+
+    async fn recycle(&self, _: &mut &'static str, _: &Metrics) -> RecycleResult<()> {
+        self.recycled.fetch_add(1, Ordering::Relaxed);
+        Ok(())
+    }
+
+    fn detach(&self, _: &mut &'static str) {
+        self.detached.fetch_add(1, Ordering::Relaxed);
+    }
+}
+
+#[tokio::test]
+async fn replace_immediate() {
+    let pool = Pool::builder(Manager::new("old"))
+        .max_size(2)
+        .build()
+        .unwrap();
+    let old_manager = pool.manager();
+    let a = pool.get().await.unwrap();
+    drop(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 2);
+
+    pool.replace_manager(Manager::new("new"), RetirePolicy::Immediate);
+    assert_eq!(pool.manager().name, "new");
+    // The idle object is removed right away
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(old_manager.detached.load(Ordering::Relaxed), 1);
+
+    assert_eq!(*pool.get().await.unwrap(), "new");
+    // The checked out object is detached from the old manager
+    drop(a);
+    assert_eq!(pool.status().size, 1);
+    assert_eq!(old_manager.detached.load(Ordering::Relaxed), 2);
+    assert_eq!(pool.manager().detached.load(Ordering::Relaxed), 0);
+}
+
+#[tokio::test]
+async fn replace_gradual() {
+    let pool = Pool::builder(Manager::new("old"))
+        .max_size(2)
+        .build()
+        .unwrap();
+    let old_manager = pool.manager();
+    {
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+    }
+
+    pool.replace_manager(Manager::new("new"), RetirePolicy::Gradual);
+    // Idle objects are kept until they are used
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(old_manager.detached.load(Ordering::Relaxed), 0);
+
+    assert_eq!(*pool.get().await.unwrap(), "new");
+    assert_eq!(old_manager.detached.load(Ordering::Relaxed), 2);
+    assert_eq!(old_manager.recycled.load(Ordering::Relaxed), 0);
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn replace_with_waiter() {
+    let pool = Pool::builder(Manager::new("old"))
+        .max_size(1)
+        .build()
+        .unwrap();
+    let a = pool.get().await.unwrap();
+    let task = {
+        let pool = pool.clone();
+        tokio::spawn(async move { *pool.get().await.unwrap() })
+    };
+    tokio::task::yield_now().await;
+    assert_eq!(pool.status().waiting, 1);
+    pool.replace_manager(Manager::new("new"), RetirePolicy::Immediate);
+    assert_eq!(pool.status().waiting, 1);
+    drop(a);
+    assert_eq!(task.await.unwrap(), "new");
+}
@@ -0,0 +1,117 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use deadpool::{
+    Runtime,
+    managed::{self, Metrics, PoolError, RecycleResult, TimeoutType},
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug, Default)]
+struct Manager {
+    created: AtomicUsize,
+    fail: bool,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = ();
+
+    async fn create(&self) -> Result<usize, ()> {
+        if self.fail {
+            return Err(());
+        }
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<()> {
+        Ok(())
+    }
+}
+
+fn pool(manager: Manager) -> Pool {
+    Pool::builder(manager)
+        .max_size(1)
+        .wait_timeout(Some(Duration::from_millis(10)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap()
+}
+
+#[tokio::test]
+async fn reserve() {
+    let pool = pool(Manager::default());
+    let reservation = pool.reserve().await.unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.available, 0);
+    assert_eq!(status.waiting, 0);
+
+    // The reserved slot isn't available to others.
+    let err = pool.get().await.unwrap_err();
+    assert!(matches!(err, PoolError::Timeout(TimeoutType::Wait, _)));
+    let err = pool.reserve().await.unwrap_err();
+    assert!(matches!(err, PoolError::Timeout(TimeoutType::Wait, _)));
+
+    let obj = reservation.get().await.unwrap();
+    assert_eq!(*obj, 0);
+    assert_eq!(pool.status().size, 1);
+    drop(obj);
+
+    // Reservations reuse idle objects.
+    let obj = pool.reserve().await.unwrap().get().await.unwrap();
+    assert_eq!(*obj, 0);
+    drop(obj);
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn not_waiting() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(1)
+        .max_waiting(Some(1))
+        .wait_timeout(Some(Duration::from_millis(10)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    drop(pool.get().await.unwrap());
+    let _reservation = pool.reserve().await.unwrap();
+    // The idle object is kept for the reservation.
+    let status = pool.status();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 0);
+    assert_eq!(status.waiting, 0);
+    // The reservation doesn't count towards `max_waiting`.
+    let err = pool.get().await.unwrap_err();
+    assert!(matches!(err, PoolError::Timeout(TimeoutType::Wait, _)));
+}
+
+#[tokio::test]
+async fn drop_reservation() {
+    let pool = pool(Manager::default());
+    let reservation = pool.reserve().await.unwrap();
+    drop(reservation);
+    let status = pool.status();
+    assert_eq!(status.size, 0);
+    assert_eq!(status.waiting, 0);
+    assert_eq!(*pool.get().await.unwrap(), 0);
+}
+
+#[tokio::test]
+async fn get_failed() {
+    let pool = pool(Manager {
+        fail: true,
+        ..Manager::default()
+    });
+    let reservation = pool.reserve().await.unwrap();
+    let err = reservation.get().await.unwrap_err();
+    assert!(matches!(err, PoolError::Backend((), _)));
+    assert_eq!(pool.status().waiting, 0);
+    // The slot was given back.
+    assert!(pool.reserve().await.is_ok());
+}
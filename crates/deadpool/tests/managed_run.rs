@@ -0,0 +1,155 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::{
+        Arc, Mutex,
+        atomic::{AtomicUsize, Ordering},
+    },
+    time::Duration,
+};
+
+use deadpool::{
+    Runtime,
+    managed::{self, DiscardReason, Metrics, PoolError, RecycleResult, RetryPolicy},
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug, PartialEq)]
+enum Error {
+    Connection,
+    Query,
+}
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+    create_errors: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Error;
+
+    async fn create(&self) -> Result<usize, Error> {
+        if self
+            .create_errors
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |n| n.checked_sub(1))
+            .is_ok()
+        {
+            return Err(Error::Connection);
+        }
+        Ok(self.created.fetch_add(1, Ordering::Relaxed))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Error> {
+        Ok(())
+    }
+
+    fn is_connection_error(&self, error: &Error) -> bool {
+        *error == Error::Connection
+    }
+}
+
+fn pool() -> (Pool, Arc<Mutex<Vec<DiscardReason>>>) {
+    let discarded = Arc::new(Mutex::new(Vec::new()));
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .on_discard({
+            let discarded = discarded.clone();
+            move |_, _, reason| discarded.lock().unwrap().push(reason)
+        })
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    (pool, discarded)
+}
+
+#[tokio::test]
+async fn run() {
+    let (pool, _) = pool();
+    let value = pool.run(async |obj| Ok(**obj + 1)).await.unwrap();
+    assert_eq!(value, 1);
+    // The object is returned to the pool right away
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn retry_connection_error() {
+    let (pool, discarded) = pool();
+    let mut ids = Vec::new();
+    let value = pool
+        .run(async |obj| {
+            ids.push(**obj);
+            if ids.len() < 3 {
+                Err(Error::Connection)
+            } else {
+                Ok("done")
+            }
+        })
+        .await
+        .unwrap();
+    assert_eq!(value, "done");
+    assert_eq!(ids, [0, 1, 2]);
+    assert_eq!(
+        discarded.lock().unwrap().as_slice(),
+        [DiscardReason::Broken, DiscardReason::Broken]
+    );
+    assert_eq!(pool.status().size, 1);
+}
+
+#[tokio::test]
+async fn retry_exhausted() {
+    let (pool, discarded) = pool();
+    let mut attempts = 0;
+    let result: Result<(), _> = pool
+        .run_with_retry(&RetryPolicy::new(2), async |_| {
+            attempts += 1;
+            Err(Error::Connection)
+        })
+        .await;
+    let Err(PoolError::Operation(Error::Connection, ctx)) = result else {
+        panic!("unexpected result: {:?}", result);
+    };
+    assert_eq!(ctx.attempts(), 2);
+    assert!(ctx.elapsed().is_some());
+    assert_eq!(ctx.status().unwrap().size, 0);
+    assert_eq!(attempts, 2);
+    assert_eq!(discarded.lock().unwrap().len(), 2);
+    assert_eq!(pool.status().size, 0);
+}
+
+#[tokio::test]
+async fn no_retry_other_error() {
+    let (pool, discarded) = pool();
+    let mut attempts = 0;
+    let result: Result<(), _> = pool
+        .run(async |_| {
+            attempts += 1;
+            Err(Error::Query)
+        })
+        .await;
+    let Err(PoolError::Operation(Error::Query, ctx)) = result else {
+        panic!("unexpected result: {:?}", result);
+    };
+    assert_eq!(ctx.attempts(), 1);
+    assert!(!ctx.is_empty());
+    assert_eq!(attempts, 1);
+    assert!(discarded.lock().unwrap().is_empty());
+    assert_eq!(pool.status().available, 1);
+}
+
+#[tokio::test]
+async fn retry_create_error() {
+    let (pool, _) = pool();
+    pool.manager().create_errors.store(1, Ordering::Relaxed);
+    let policy = RetryPolicy {
+        max_attempts: 2,
+        delay: Some(Duration::from_millis(10)),
+    };
+    let task = {
+        let pool = pool.clone();
+        tokio::spawn(async move { pool.run_with_retry(&policy, async |obj| Ok(**obj)).await })
+    };
+    assert_eq!(task.await.unwrap().unwrap(), 0);
+}
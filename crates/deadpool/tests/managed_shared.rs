@@ -0,0 +1,97 @@
+#![cfg(feature = "managed")]
+
+use std::{
+    convert::Infallible,
+    sync::atomic::{AtomicUsize, Ordering},
+    time::Duration,
+};
+
+use deadpool::managed::{self, Metrics, RecycleResult, SharedObject, SharedPool};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(self.created.fetch_add(1, Ordering::SeqCst))
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+fn shared_pool(max_size: usize, max_borrowers: usize) -> SharedPool<Manager> {
+    let pool = Pool::builder(Manager::default())
+        .max_size(max_size)
+        .build()
+        .unwrap();
+    SharedPool::new(pool, max_borrowers)
+}
+
+#[tokio::test]
+async fn least_loaded() {
+    let pool = shared_pool(2, 3);
+    let a = pool.get().await.unwrap();
+    let b = pool.get().await.unwrap();
+    let c = pool.get().await.unwrap();
+    // A new object is only created once the first one is at capacity.
+    assert_eq!([*a, *b, *c], [0, 0, 0]);
+    assert_eq!(pool.pool().status().size, 1);
+    let d = pool.get().await.unwrap();
+    let e = pool.get().await.unwrap();
+    assert_eq!([*d, *e], [1, 1]);
+    drop(a);
+    drop(b);
+    // The least loaded object is leased.
+    let f = pool.get().await.unwrap();
+    assert_eq!(*f, 0);
+    let leases: Vec<_> = pool.leases().into_iter().map(|(_, n)| n).collect();
+    assert_eq!(leases, [2, 2]);
+    drop((c, d, e, f));
+    assert!(pool.leases().is_empty());
+    assert_eq!(pool.pool().status().available, 2);
+}
+
+#[tokio::test]
+async fn clones_share_lease() {
+    let pool = shared_pool(1, 1);
+    let a = pool.get().await.unwrap();
+    let b = a.clone();
+    drop(a);
+    assert_eq!(pool.leases(), [(SharedObject::id(&b), 1)]);
+    drop(b);
+    assert!(pool.leases().is_empty());
+}
+
+#[tokio::test]
+async fn wait_for_lease() {
+    let pool = shared_pool(1, 2);
+    let a = pool.get().await.unwrap();
+    let _b = pool.get().await.unwrap();
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { *pool.get().await.unwrap() }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!waiter.is_finished());
+    drop(a);
+    assert_eq!(waiter.await.unwrap(), 0);
+    assert_eq!(pool.pool().manager().created.load(Ordering::SeqCst), 1);
+}
+
+#[tokio::test]
+async fn close() {
+    let pool = shared_pool(1, 1);
+    let _a = pool.get().await.unwrap();
+    pool.close();
+    assert!(pool.is_closed());
+    assert!(pool.get().await.is_err());
+}
@@ -47,6 +47,7 @@ async fn test_managed_timeout(runtime: Runtime) {
             create: Some(Duration::from_millis(0)),
             wait: Some(Duration::from_millis(0)),
             recycle: Some(Duration::from_millis(0)),
+            total: None,
         },
         ..Default::default()
     };
@@ -56,7 +57,7 @@ async fn test_managed_timeout(runtime: Runtime) {
         .build()
         .unwrap();
 
-    assert!(matches!(pool.get().await, Err(PoolError::Timeout(_))));
+    assert!(matches!(pool.get().await, Err(PoolError::Timeout(_, _))));
 }
 
 #[cfg(feature = "rt_tokio_1")]
@@ -0,0 +1,84 @@
+#![cfg(all(feature = "managed", feature = "rt_tokio_1"))]
+
+use std::{
+    sync::atomic::{AtomicUsize, Ordering},
+    time::{Duration, Instant},
+};
+
+use deadpool::{
+    Runtime,
+    managed::{self, Metrics, PoolError, RecycleError, RecycleResult, TimeoutType},
+};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    recycled: AtomicUsize,
+}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = ();
+
+    async fn create(&self) -> Result<(), ()> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<()> {
+        let _ = self.recycled.fetch_add(1, Ordering::Relaxed);
+        tokio::time::sleep(Duration::from_millis(30)).await;
+        Err(RecycleError::message("broken"))
+    }
+}
+
+async fn pool(total: Option<Duration>) -> Pool {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .total_timeout(total)
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    let objs = [
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    drop(objs);
+    pool
+}
+
+#[tokio::test]
+async fn total_covers_recycle_loop() {
+    let pool = pool(Some(Duration::from_millis(50))).await;
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Timeout(TimeoutType::Total, _))
+    ));
+    assert_eq!(pool.manager().recycled.load(Ordering::Relaxed), 2);
+}
+
+#[tokio::test]
+async fn get_until() {
+    let pool = pool(None).await;
+    let deadline = Instant::now() + Duration::from_millis(50);
+    assert!(matches!(
+        pool.get_until(deadline).await,
+        Err(PoolError::Timeout(TimeoutType::Total, _))
+    ));
+    assert_eq!(pool.manager().recycled.load(Ordering::Relaxed), 2);
+    // Without a deadline the remaining idle objects are recycled before a
+    // new one is created.
+    assert!(pool.get().await.is_ok());
+    assert_eq!(pool.manager().recycled.load(Ordering::Relaxed), 4);
+}
+
+#[tokio::test]
+async fn get_until_requires_runtime() {
+    let pool = Pool::builder(Manager::default()).build().unwrap();
+    assert!(matches!(
+        pool.get_until(Instant::now()).await,
+        Err(PoolError::NoRuntimeSpecified(_))
+    ));
+}
@@ -0,0 +1,118 @@
+#![cfg(feature = "managed")]
+
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use deadpool::managed::{self, Metrics, Object, PoolError, RecycleResult, WarmUpError};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Default)]
+struct Manager {
+    created: AtomicUsize,
+    in_flight: AtomicUsize,
+    max_in_flight: AtomicUsize,
+    fail_at: Option<usize>,
+}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = &'static str;
+
+    async fn create(&self) -> Result<usize, &'static str> {
+        let id = self.created.fetch_add(1, Ordering::SeqCst);
+        let in_flight = self.in_flight.fetch_add(1, Ordering::SeqCst) + 1;
+        let _ = self.max_in_flight.fetch_max(in_flight, Ordering::SeqCst);
+        tokio::task::yield_now().await;
+        let _ = self.in_flight.fetch_sub(1, Ordering::SeqCst);
+        if self.fail_at == Some(id) {
+            return Err("create failed");
+        }
+        Ok(id)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<&'static str> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn build_and_warm() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .build_and_warm(3)
+        .await
+        .unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 3);
+    assert_eq!(status.available, 3);
+    assert_eq!(pool.manager().created.load(Ordering::SeqCst), 3);
+    assert_eq!(pool.manager().max_in_flight.load(Ordering::SeqCst), 3);
+}
+
+#[tokio::test]
+async fn build_and_warm_capped() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(2)
+        .build_and_warm(5)
+        .await
+        .unwrap();
+    assert_eq!(pool.status().size, 2);
+    assert_eq!(pool.manager().created.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn build_and_warm_error() {
+    let manager = Manager {
+        fail_at: Some(1),
+        ..Manager::default()
+    };
+    let result = Pool::builder(manager).max_size(4).build_and_warm(3).await;
+    assert!(matches!(
+        result,
+        Err(WarmUpError::Pool(PoolError::Backend("create failed", _)))
+    ));
+}
+
+#[tokio::test]
+async fn warm_up_refill() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .build_and_warm(3)
+        .await
+        .unwrap();
+    let obj = pool.get().await.unwrap();
+    let _ = Object::take(pool.get().await.unwrap());
+    assert_eq!(pool.status().size, 2);
+    pool.warm_up(3).await.unwrap();
+    let status = pool.status();
+    assert_eq!(status.size, 3);
+    assert_eq!(status.available, 2);
+    assert_eq!(pool.manager().created.load(Ordering::SeqCst), 4);
+    drop(obj);
+    pool.warm_up(2).await.unwrap();
+    assert_eq!(pool.status().size, 3);
+    assert_eq!(pool.manager().created.load(Ordering::SeqCst), 4);
+}
+
+#[tokio::test]
+async fn build_and_warm_max_uses() {
+    let pool = Pool::builder(Manager::default())
+        .max_size(4)
+        .max_uses(Some(1))
+        .build_and_warm(3)
+        .await
+        .unwrap();
+    assert_eq!(pool.status().size, 3);
+    assert_eq!(pool.status().available, 3);
+    // Every warmed up object is handed out once before being discarded.
+    let objs = [
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+        pool.get().await.unwrap(),
+    ];
+    assert_eq!(pool.manager().created.load(Ordering::SeqCst), 3);
+    drop(objs);
+    assert_eq!(pool.status().size, 0);
+    pool.warm_up(2).await.unwrap();
+    assert_eq!(pool.status().size, 2);
+}
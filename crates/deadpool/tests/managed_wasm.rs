@@ -0,0 +1,65 @@
+#![cfg(all(feature = "managed", target_arch = "wasm32"))]
+
+use std::{convert::Infallible, time::Duration};
+
+use wasm_bindgen_test::wasm_bindgen_test;
+
+use deadpool::managed::{self, Metrics, Object, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+/// There is no timer available without a runtime which is why this
+/// simply waits for the clock to advance.
+fn wait_for(duration: Duration) {
+    let start = web_time::Instant::now();
+    while start.elapsed() < duration {}
+}
+
+#[wasm_bindgen_test]
+async fn metrics() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    let metrics = *Object::metrics(&obj);
+    assert_eq!(metrics.recycle_count, 0);
+    assert!(metrics.recycled.is_none());
+    drop(obj);
+
+    wait_for(Duration::from_millis(10));
+    let obj = pool.get().await.unwrap();
+    let metrics = Object::metrics(&obj);
+    assert_eq!(metrics.recycle_count, 1);
+    assert!(metrics.recycled.is_some());
+    assert!(metrics.age() >= Duration::from_millis(10));
+    assert!(metrics.last_used() < metrics.age());
+}
+
+#[wasm_bindgen_test]
+async fn retain_by_age() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    {
+        let _a = pool.get().await.unwrap();
+    }
+    wait_for(Duration::from_millis(10));
+    {
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+    }
+    let result = pool.retain(|_, metrics| metrics.age() < Duration::from_millis(10));
+    assert_eq!(result.retained, 1);
+    assert_eq!(result.removed.len(), 1);
+}
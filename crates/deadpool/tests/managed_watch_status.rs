@@ -0,0 +1,101 @@
+#![cfg(feature = "managed")]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::managed::{self, Metrics, PoolError, RecycleResult};
+
+type Pool = managed::Pool<Manager>;
+
+#[derive(Debug)]
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = ();
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<(), Infallible> {
+        Ok(())
+    }
+
+    async fn recycle(&self, _: &mut (), _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn watch_status() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let mut rx = pool.watch_status();
+    assert_eq!(*rx.borrow_and_update(), pool.status());
+
+    let obj = pool.get().await.unwrap();
+    assert!(rx.has_changed().unwrap());
+    let status = *rx.borrow_and_update();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 0);
+
+    drop(obj);
+    assert!(rx.has_changed().unwrap());
+    let status = *rx.borrow_and_update();
+    assert_eq!(status.size, 1);
+    assert_eq!(status.available, 1);
+
+    pool.resize(0);
+    assert!(rx.has_changed().unwrap());
+    assert_eq!(rx.borrow_and_update().size, 0);
+}
+
+#[tokio::test]
+async fn wait_for_available_warm_up() {
+    let pool = Pool::builder(Manager {}).max_size(3).build().unwrap();
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.wait_for_available(2).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!waiter.is_finished());
+    pool.warm_up(2).await.unwrap();
+    tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_available_return() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let obj = pool.get().await.unwrap();
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.wait_for_available(1).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    assert!(!waiter.is_finished());
+    drop(obj);
+    tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .unwrap()
+        .unwrap()
+        .unwrap();
+}
+
+#[tokio::test]
+async fn wait_for_available_closed() {
+    let pool = Pool::builder(Manager {}).max_size(1).build().unwrap();
+    let waiter = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.wait_for_available(1).await }
+    });
+    tokio::time::sleep(Duration::from_millis(10)).await;
+    pool.close();
+    let result = tokio::time::timeout(Duration::from_secs(1), waiter)
+        .await
+        .unwrap()
+        .unwrap();
+    assert!(matches!(result, Err(PoolError::Closed(_))));
+    assert!(matches!(
+        pool.wait_for_available(1).await,
+        Err(PoolError::Closed(_))
+    ));
+}
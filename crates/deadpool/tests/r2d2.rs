@@ -0,0 +1,47 @@
+#![cfg(feature = "r2d2")]
+
+use std::{convert::Infallible, time::Duration};
+
+use deadpool::{
+    managed::{self, Metrics, RecycleResult},
+    r2d2::PoolManager,
+};
+
+type Pool = managed::Pool<Manager>;
+
+struct Manager {}
+
+impl managed::Manager for Manager {
+    type Type = usize;
+    type Error = Infallible;
+
+    async fn create(&self) -> Result<usize, Infallible> {
+        Ok(42)
+    }
+
+    async fn recycle(&self, _: &mut usize, _: &Metrics) -> RecycleResult<Infallible> {
+        Ok(())
+    }
+}
+
+#[test]
+fn shared_pool() {
+    let pool = Pool::builder(Manager {}).max_size(2).build().unwrap();
+    let r2d2_pool = r2d2::Pool::builder()
+        .max_size(1)
+        .min_idle(Some(0))
+        .connection_timeout(Duration::from_secs(1))
+        .build(PoolManager::new(pool.clone()))
+        .unwrap();
+    {
+        let conn = r2d2_pool.get().unwrap();
+        assert_eq!(**conn, 42);
+        let status = pool.status();
+        assert_eq!(status.size, 1);
+        assert_eq!(status.available, 0);
+        // The other object is still available to async users.
+        assert_eq!(*pool.blocking_get().unwrap(), 42);
+    }
+    assert_eq!(r2d2_pool.state().idle_connections, 1);
+    assert_eq!(pool.status().available, 1);
+}
@@ -0,0 +1,80 @@
+#![cfg(all(feature = "testing", feature = "rt_tokio_1"))]
+
+use std::time::Duration;
+
+use deadpool::{
+    Runtime,
+    managed::{self, Object, PoolError, TimeoutType},
+    testing::{self, MockError, MockManager},
+};
+
+type Pool = managed::Pool<MockManager>;
+
+#[tokio::test]
+async fn scripted_create() {
+    let mgr = MockManager::new();
+    mgr.push_create_result(Err(MockError::new("first")));
+    mgr.push_create_result(Err(MockError::new("second")));
+    let pool = Pool::builder(mgr).max_size(1).build().unwrap();
+    assert!(
+        matches!(pool.get().await, Err(PoolError::Backend(e, _)) if e == MockError::new("first"))
+    );
+    assert!(
+        matches!(pool.get().await, Err(PoolError::Backend(e, _)) if e == MockError::new("second"))
+    );
+    assert_eq!(pool.get().await.unwrap().id(), 0);
+    assert_eq!(pool.manager().create_calls(), 3);
+    testing::assert_idle(&pool, 1);
+    testing::assert_no_leaks(&pool);
+}
+
+#[tokio::test]
+async fn scripted_recycle() {
+    let pool = Pool::builder(MockManager::new())
+        .max_size(2)
+        .build()
+        .unwrap();
+    {
+        let _a = pool.get().await.unwrap();
+        let _b = pool.get().await.unwrap();
+    }
+    pool.manager()
+        .push_recycle_result(Err(MockError::new("broken")));
+    let obj = pool.get().await.unwrap();
+    assert_eq!(obj.id(), 0);
+    assert_eq!(pool.manager().recycle_calls(), 2);
+    assert_eq!(pool.manager().detach_calls(), 1);
+    drop(obj);
+    testing::assert_idle(&pool, 1);
+    testing::assert_no_leaks(&pool);
+}
+
+#[tokio::test]
+async fn latency() {
+    let mgr = MockManager::new().runtime(Runtime::Tokio1);
+    mgr.set_create_latency(Some(Duration::from_millis(100)));
+    let pool = Pool::builder(mgr)
+        .max_size(1)
+        .create_timeout(Some(Duration::from_millis(10)))
+        .runtime(Runtime::Tokio1)
+        .build()
+        .unwrap();
+    assert!(matches!(
+        pool.get().await,
+        Err(PoolError::Timeout(TimeoutType::Create, _))
+    ));
+    pool.manager().set_create_latency(None);
+    assert!(pool.get().await.is_ok());
+}
+
+#[tokio::test]
+#[should_panic(expected = "1 objects are alive but the pool only holds 0")]
+async fn leak_detected() {
+    let pool = Pool::builder(MockManager::new())
+        .max_size(1)
+        .build()
+        .unwrap();
+    let obj = Object::take(pool.get().await.unwrap());
+    testing::assert_no_leaks(&pool);
+    drop(obj);
+}
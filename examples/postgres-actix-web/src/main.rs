@@ -1,6 +1,7 @@
 use actix_web::{error, get, web, App, HttpResponse, HttpServer};
 use config::ConfigError;
-use deadpool_postgres::{Client, Pool, PoolError, Runtime};
+use deadpool::actix_web::Conn;
+use deadpool_postgres::{Client, Pool, Runtime};
 use dotenvy::dotenv;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -30,13 +31,12 @@ struct Event {
 #[derive(Debug, thiserror::Error)]
 enum Error {
     #[error("An internal error occurred. Please try again later.")]
-    PoolError(#[from] PoolError),
+    PgError(#[from] tokio_postgres::Error),
 }
 
 impl error::ResponseError for Error {}
 
-async fn event_list(pool: &Pool) -> Result<Vec<Event>, PoolError> {
-    let client: Client = pool.get().await?;
+async fn event_list(client: &Client) -> Result<Vec<Event>, tokio_postgres::Error> {
     let stmt = client.prepare_cached("SELECT id, title FROM event").await?;
     let rows = client.query(&stmt, &[]).await?;
     Ok(rows
@@ -49,8 +49,8 @@ async fn event_list(pool: &Pool) -> Result<Vec<Event>, PoolError> {
 }
 
 #[get("/v1.0/event.list")]
-async fn index(db_pool: web::Data<Pool>) -> Result<HttpResponse, Error> {
-    let events = event_list(&db_pool).await?;
+async fn index(client: Conn<Pool>) -> Result<HttpResponse, Error> {
+    let events = event_list(&client).await?;
     Ok(HttpResponse::Ok().json(events))
 }
 
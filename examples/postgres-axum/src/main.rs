@@ -1,5 +1,6 @@
+use std::time::Duration;
+
 use axum::{
-    extract::State,
     http::StatusCode,
     response::{IntoResponse, Response},
     routing::get,
@@ -7,7 +8,8 @@ use axum::{
 };
 use axum_macros::debug_handler;
 use config::ConfigError;
-use deadpool_postgres::{Client, Pool, PoolError, Runtime};
+use deadpool::axum::{CheckoutLayer, Conn};
+use deadpool_postgres::{Pool, Runtime};
 use dotenvy::dotenv;
 use serde::{Deserialize, Serialize};
 use uuid::Uuid;
@@ -36,8 +38,6 @@ struct Event {
 
 #[derive(Debug, thiserror::Error)]
 enum Error {
-    #[error("Pool error: {0}")]
-    PoolError(#[from] PoolError),
     #[error("PostgreSQL error: {0}")]
     PgError(#[from] tokio_postgres::Error),
 }
@@ -52,9 +52,8 @@ impl IntoResponse for Error {
     }
 }
 
-#[debug_handler]
-async fn event_list(pool: State<Pool>) -> Result<Json<Vec<Event>>, Error> {
-    let client: Client = pool.get().await?;
+#[debug_handler(state = Pool)]
+async fn event_list(client: Conn<Pool>) -> Result<Json<Vec<Event>>, Error> {
     let stmt = client.prepare_cached("SELECT id, title FROM event").await?;
     let rows = client.query(&stmt, &[]).await?;
     let events = rows
@@ -77,6 +76,7 @@ async fn main() {
         .unwrap();
     let app = Router::new()
         .route("/v1.0/event.list", get(event_list))
+        .layer(CheckoutLayer::new().deadline(Duration::from_secs(5)))
         .with_state(pool);
     let listener = tokio::net::TcpListener::bind(&config.listen).await.unwrap();
     println!("Server running at http://{}/", &config.listen);
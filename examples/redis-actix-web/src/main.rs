@@ -1,7 +1,11 @@
 use std::env;
 
 use actix_web::{error, get, middleware, web, App, Error, HttpResponse, HttpServer};
-use deadpool_redis::{redis::cmd, Config as RedisConfig, Connection, Pool, PoolError, Runtime};
+use deadpool::actix_web::Conn;
+use deadpool_redis::{
+    redis::{cmd, RedisError},
+    Config as RedisConfig, Connection, Pool, Runtime,
+};
 
 fn redis_uri() -> String {
     match env::var("REDIS_URL") {
@@ -10,18 +14,17 @@ fn redis_uri() -> String {
     }
 }
 
-async fn redis_ping(pool: &Pool) -> Result<String, PoolError> {
-    let mut conn: Connection = pool.get().await?;
-    let pong: String = cmd("PING").query_async(&mut conn).await?;
+async fn redis_ping(conn: &mut Connection) -> Result<String, RedisError> {
+    let pong: String = cmd("PING").query_async(conn).await?;
 
     Ok(pong)
 }
 
 #[get("/")]
-async fn index(redis_pool: web::Data<Pool>) -> Result<HttpResponse, Error> {
-    let pong = redis_ping(&redis_pool)
+async fn index(mut conn: Conn<Pool>) -> Result<HttpResponse, Error> {
+    let pong = redis_ping(&mut conn)
         .await
-        .map_err(|pool_error| error::ErrorNotAcceptable(format!("{pool_error}")))?;
+        .map_err(|redis_error| error::ErrorNotAcceptable(format!("{redis_error}")))?;
 
     Ok(HttpResponse::Ok().body(format!("Redis PING -> {pong}")))
 }